@@ -16,7 +16,18 @@
  * You should have received a copy of the GNU General Public License along with
  * libaustralis. If not, see <https://www.gnu.org/licenses/>.
  */
+pub mod achievements;
 pub mod assets;
+pub mod dashlaunch;
+pub mod device_path;
+pub mod filebrowser;
 pub mod ftp;
 pub mod http;
 pub mod http_schemas;
+pub mod live_cache;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+pub mod screencapture_sync;
+pub mod telemetry;
+pub mod threads;
+pub mod watcher;
@@ -0,0 +1,185 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::collections::HashMap;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas;
+use crate::utils::GenericResult;
+
+pub const PLAYER_SLOTS: usize = 4;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AchievementProgress {
+    pub id: u32,
+    pub image_id: u32,
+    pub caption: String,
+    pub description: String,
+    pub unachieved: String,
+    pub cred: u32,
+    pub hidden: bool,
+    pub unlocked: bool,
+    // the raw per-player value from `/achievement/player`. only zero is known
+    // to mean locked, what a non-zero value encodes is not confirmed yet.
+    pub player_value: u32,
+    // seconds since the unix epoch. always `None` until the player value is
+    // known to carry the unlock time.
+    pub unlocked_at: Option<u32>,
+}
+
+impl AchievementProgress {
+    pub async fn image(&self, client: &HttpClient, token: Option<&str>) -> GenericResult<Vec<u8>> {
+        client
+            .get_image_achievement(token, &format!("{}", self.image_id))
+            .await
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct PlayerAchievements {
+    pub player: usize,
+    pub profile: Option<http_schemas::Profile>,
+    pub achievements: Vec<AchievementProgress>,
+}
+
+impl PlayerAchievements {
+    pub fn earned_gamerscore(&self) -> u32 {
+        self.achievements
+            .iter()
+            .filter(|x| x.unlocked)
+            .map(|x| x.cred)
+            .sum()
+    }
+
+    pub fn possible_gamerscore(&self) -> u32 {
+        self.achievements.iter().map(|x| x.cred).sum()
+    }
+
+    pub fn unlocked_count(&self) -> usize {
+        self.achievements.iter().filter(|x| x.unlocked).count()
+    }
+
+    pub fn total_count(&self) -> usize {
+        self.achievements.len()
+    }
+}
+
+pub async fn get_achievement_progress(
+    client: &HttpClient,
+    token: Option<&str>,
+) -> GenericResult<Vec<PlayerAchievements>> {
+    let achievements = client.get_achievement(token).await?;
+    // the player endpoint has nothing to report when the title has no
+    // achievements, so do not treat that as an error
+    if achievements.is_empty() {
+        return Ok(merge_achievement_progress(&[], &[], &[]));
+    }
+    let players = client.get_achievement_player(token).await?;
+    let profiles = client.get_profile(token).await?;
+    Ok(merge_achievement_progress(
+        &achievements,
+        &players,
+        &profiles,
+    ))
+}
+
+// joins the achievement definitions with the per-player unlock values,
+// treating any non-zero player value as unlocked
+pub fn merge_achievement_progress(
+    achievements: &[http_schemas::Achievement],
+    players: &[http_schemas::AchievementPlayer],
+    profiles: &[http_schemas::Profile],
+) -> Vec<PlayerAchievements> {
+    let player_values: HashMap<u32, [u32; PLAYER_SLOTS]> =
+        players.iter().map(|x| (x.id, x.player)).collect();
+    (0..PLAYER_SLOTS)
+        .map(|player| PlayerAchievements {
+            player,
+            profile: profiles
+                .iter()
+                .find(|x| x.index as usize == player && x.signedin != 0)
+                .cloned(),
+            achievements: achievements
+                .iter()
+                .map(|achievement| {
+                    let value = player_values
+                        .get(&achievement.id)
+                        .map(|x| x[player])
+                        .unwrap_or(0);
+                    AchievementProgress {
+                        id: achievement.id,
+                        image_id: achievement.imageid,
+                        caption: achievement.strings.caption.clone(),
+                        description: achievement.strings.description.clone(),
+                        unachieved: achievement.strings.unachieved.clone(),
+                        cred: achievement.cred,
+                        hidden: achievement.hidden != 0,
+                        unlocked: value != 0,
+                        player_value: value,
+                        unlocked_at: None,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_raw_player_values() {
+        let achievements: Vec<http_schemas::Achievement> = serde_json::from_str(
+            r#"[
+                {"cred": 10, "hidden": 0, "id": 1, "imageid": 11, "strings": {"caption": "First", "description": "", "unachieved": ""}, "type": 0},
+                {"cred": 20, "hidden": 1, "id": 2, "imageid": 12, "strings": {"caption": "Second", "description": "", "unachieved": ""}, "type": 0},
+                {"cred": 30, "hidden": 0, "id": 3, "imageid": 13, "strings": {"caption": "Third", "description": "", "unachieved": ""}, "type": 0}
+            ]"#,
+        )
+        .unwrap();
+        let players: Vec<http_schemas::AchievementPlayer> = serde_json::from_str(
+            r#"[{"id": 1, "player": [1, 0, 0, 0]}, {"id": 2, "player": [1700000000, 0, 0, 0]}]"#,
+        )
+        .unwrap();
+        let profiles: Vec<http_schemas::Profile> = serde_json::from_str(
+            r#"[{"gamerscore": 30, "gamertag": "Player", "index": 0, "signedin": 1, "xuid": "0009000000000000"}]"#,
+        )
+        .unwrap();
+        let merged = merge_achievement_progress(&achievements, &players, &profiles);
+        assert_eq!(merged.len(), PLAYER_SLOTS);
+        let first = &merged[0];
+        assert_eq!(first.profile.as_ref().unwrap().gamertag, "Player");
+        assert_eq!(
+            first
+                .achievements
+                .iter()
+                .map(|x| (x.unlocked, x.player_value, x.unlocked_at))
+                .collect::<Vec<_>>(),
+            [(true, 1, None), (true, 1700000000, None), (false, 0, None)]
+        );
+        assert!(first.achievements[1].hidden);
+        assert_eq!(first.earned_gamerscore(), 30);
+        assert_eq!(first.possible_gamerscore(), 60);
+        // slots without a signed in profile still list every achievement
+        assert!(merged[1].profile.is_none());
+        assert_eq!(merged[1].unlocked_count(), 0);
+        assert_eq!(merged[1].total_count(), 3);
+    }
+}
@@ -0,0 +1,301 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas;
+use crate::utils::{create_parent_directories, GenericResult};
+use log::error;
+
+// options launching a title from a controller button
+const HOTKEY_OPTIONS: [&str; 14] = [
+    "but_a", "but_b", "but_x", "but_y", "start", "back", "lbump", "rbump", "lthumb", "rthumb",
+    "up", "down", "left", "right",
+];
+
+// numeric options that must not be read as booleans when set to 0 or 1
+const INTEGER_OPTIONS: [&str; 6] = [
+    "autocont",
+    "fakelevel",
+    "fatalfreeze",
+    "region",
+    "temptime",
+    "xhttpport",
+];
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum DashlaunchValue {
+    Boolean(bool),
+    Integer(i64),
+    Path(String),
+    Hotkey(String),
+    Text(String),
+}
+
+impl fmt::Display for DashlaunchValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DashlaunchValue::Boolean(x) => write!(f, "{}", x),
+            DashlaunchValue::Integer(x) => write!(f, "{}", x),
+            DashlaunchValue::Path(x) => write!(f, "{}", x),
+            DashlaunchValue::Hotkey(x) => write!(f, "{}", x),
+            DashlaunchValue::Text(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl DashlaunchValue {
+    pub fn interpret(name: &str, value: &str) -> Self {
+        let name = name.to_lowercase();
+        let trimmed = value.trim();
+        if HOTKEY_OPTIONS.contains(&name.as_str()) {
+            return Self::Hotkey(String::from(trimmed));
+        }
+        if is_device_path(trimmed) {
+            return Self::Path(String::from(trimmed));
+        }
+        match trimmed.to_lowercase().as_str() {
+            "true" | "yes" | "on" => return Self::Boolean(true),
+            "false" | "no" | "off" => return Self::Boolean(false),
+            _ => (),
+        }
+        match parse_integer(trimmed) {
+            Some(0) if !INTEGER_OPTIONS.contains(&name.as_str()) => Self::Boolean(false),
+            Some(1) if !INTEGER_OPTIONS.contains(&name.as_str()) => Self::Boolean(true),
+            Some(x) => Self::Integer(x),
+            None => Self::Text(String::from(value)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DashlaunchTypedOption {
+    pub category: String,
+    pub name: String,
+    pub raw: String,
+    pub value: DashlaunchValue,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DashlaunchSnapshotVersion {
+    pub kernel: u32,
+    pub major: u32,
+    pub minor: u32,
+    pub build: u32,
+}
+
+// raw option values grouped by category and keyed by option name, so that a
+// snapshot can be written to and read from TOML without losing information
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DashlaunchSnapshot {
+    pub version: Option<DashlaunchSnapshotVersion>,
+    pub options: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum DashlaunchDifference {
+    Added {
+        category: String,
+        name: String,
+        value: DashlaunchValue,
+    },
+    Removed {
+        category: String,
+        name: String,
+        value: DashlaunchValue,
+    },
+    Changed {
+        category: String,
+        name: String,
+        left: DashlaunchValue,
+        right: DashlaunchValue,
+    },
+}
+
+impl fmt::Display for DashlaunchDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DashlaunchDifference::Added {
+                category,
+                name,
+                value,
+            } => write!(f, "+ [{}] {} = {}", category, name, value),
+            DashlaunchDifference::Removed {
+                category,
+                name,
+                value,
+            } => write!(f, "- [{}] {} = {}", category, name, value),
+            DashlaunchDifference::Changed {
+                category,
+                name,
+                left,
+                right,
+            } => write!(f, "~ [{}] {}: {} -> {}", category, name, left, right),
+        }
+    }
+}
+
+impl From<&http_schemas::Dashlaunch> for DashlaunchSnapshot {
+    fn from(dashlaunch: &http_schemas::Dashlaunch) -> Self {
+        let mut options: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        for option in dashlaunch.options.iter() {
+            options
+                .entry(option.category.clone())
+                .or_default()
+                .insert(option.name.clone(), option.value.clone());
+        }
+        Self {
+            version: Some(DashlaunchSnapshotVersion {
+                kernel: dashlaunch.version.kernel,
+                major: dashlaunch.version.number.major,
+                minor: dashlaunch.version.number.minor,
+                build: dashlaunch.version.number.build,
+            }),
+            options,
+        }
+    }
+}
+
+impl DashlaunchSnapshot {
+    pub async fn fetch(client: &HttpClient, token: Option<&str>) -> GenericResult<Self> {
+        Ok(Self::from(&client.get_dashlaunch(token).await?))
+    }
+
+    pub fn from_toml(toml_str: &str) -> GenericResult<Self> {
+        match toml::from_str(toml_str) {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                let msg = "Failed to parse DashlaunchSnapshot from TOML.";
+                error!("{} Got the following error: {}", msg, err);
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub fn to_toml(&self) -> GenericResult<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    pub fn load(file_path: &Path) -> GenericResult<Self> {
+        Self::from_toml(&std::fs::read_to_string(file_path)?)
+    }
+
+    pub fn save(&self, file_path: &Path) -> GenericResult<()> {
+        create_parent_directories(file_path)?;
+        std::fs::write(file_path, self.to_toml()?)?;
+        Ok(())
+    }
+
+    pub fn typed_options(&self) -> Vec<DashlaunchTypedOption> {
+        self.options
+            .iter()
+            .flat_map(|(category, options)| {
+                options.iter().map(|(name, raw)| DashlaunchTypedOption {
+                    category: category.clone(),
+                    name: name.clone(),
+                    raw: raw.clone(),
+                    value: DashlaunchValue::interpret(name, raw),
+                })
+            })
+            .collect()
+    }
+
+    pub fn by_category(&self) -> BTreeMap<String, Vec<DashlaunchTypedOption>> {
+        let mut categories: BTreeMap<String, Vec<DashlaunchTypedOption>> = BTreeMap::new();
+        for option in self.typed_options() {
+            categories
+                .entry(option.category.clone())
+                .or_default()
+                .push(option);
+        }
+        categories
+    }
+
+    pub fn get(&self, category: &str, name: &str) -> Option<DashlaunchValue> {
+        self.options
+            .get(category)
+            .and_then(|x| x.get(name))
+            .map(|raw| DashlaunchValue::interpret(name, raw))
+    }
+
+    // values are compared after interpretation, so `1` and `true` are equal
+    pub fn diff(&self, other: &Self) -> Vec<DashlaunchDifference> {
+        let left = self.typed_options();
+        let right = other.typed_options();
+        let mut differences = Vec::new();
+        for l in left.iter() {
+            match right
+                .iter()
+                .find(|r| r.category == l.category && r.name == l.name)
+            {
+                Some(r) if r.value != l.value => differences.push(DashlaunchDifference::Changed {
+                    category: l.category.clone(),
+                    name: l.name.clone(),
+                    left: l.value.clone(),
+                    right: r.value.clone(),
+                }),
+                Some(_) => (),
+                None => differences.push(DashlaunchDifference::Removed {
+                    category: l.category.clone(),
+                    name: l.name.clone(),
+                    value: l.value.clone(),
+                }),
+            }
+        }
+        for r in right.iter() {
+            if !left
+                .iter()
+                .any(|l| l.category == r.category && l.name == r.name)
+            {
+                differences.push(DashlaunchDifference::Added {
+                    category: r.category.clone(),
+                    name: r.name.clone(),
+                    value: r.value.clone(),
+                });
+            }
+        }
+        differences
+    }
+}
+
+fn is_device_path(value: &str) -> bool {
+    match value.split_once(':') {
+        Some((drive, rest)) => {
+            !drive.is_empty()
+                && drive.chars().all(|c| c.is_ascii_alphanumeric())
+                && (rest.starts_with('\\') || rest.starts_with('/'))
+        }
+        None => false,
+    }
+}
+
+fn parse_integer(value: &str) -> Option<i64> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => value.parse::<i64>().ok(),
+    }
+}
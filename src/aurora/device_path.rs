@@ -0,0 +1,138 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::fmt;
+use std::str::FromStr;
+
+use crate::utils::{GenericError, GenericResult};
+
+// a path on an Xbox 360 device, for example `Hdd1:\Games\Halo 3\default.xex`
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DevicePath {
+    pub drive: String,
+    pub components: Vec<String>,
+}
+
+impl fmt::Display for DevicePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:\\{}", self.drive, self.components.join("\\"))
+    }
+}
+
+impl FromStr for DevicePath {
+    type Err = GenericError;
+
+    fn from_str(path: &str) -> GenericResult<Self> {
+        let (drive, rest) = match path.split_once(':') {
+            Some(x) => x,
+            None => {
+                return Err(format!(
+                    "Invalid device path '{}'. Expected a drive such as 'Hdd1:'.",
+                    path
+                )
+                .into())
+            }
+        };
+        let mut device_path = Self::new(drive)?;
+        for component in rest.split(['\\', '/']) {
+            if !component.is_empty() {
+                device_path.push(component)?;
+            }
+        }
+        Ok(device_path)
+    }
+}
+
+impl DevicePath {
+    pub fn new(drive: &str) -> GenericResult<Self> {
+        validate_part(drive, "drive")?;
+        Ok(Self {
+            drive: String::from(drive),
+            components: Vec::new(),
+        })
+    }
+
+    pub fn from_parts(drive: &str, components: &[&str]) -> GenericResult<Self> {
+        let mut device_path = Self::new(drive)?;
+        for component in components {
+            device_path.push(component)?;
+        }
+        Ok(device_path)
+    }
+
+    pub fn join(&self, component: &str) -> GenericResult<Self> {
+        let mut device_path = self.clone();
+        device_path.push(component)?;
+        Ok(device_path)
+    }
+
+    pub fn push(&mut self, component: &str) -> GenericResult<()> {
+        validate_part(component, "path component")?;
+        self.components.push(String::from(component));
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<String> {
+        self.components.pop()
+    }
+
+    pub fn file_name(&self) -> Option<&str> {
+        self.components.last().map(|x| x.as_str())
+    }
+
+    pub fn parent(&self) -> Option<Self> {
+        let mut parent = self.clone();
+        parent.pop().map(|_| parent)
+    }
+
+    // drive followed by the components, as taken by `TelnetClient::dirlist`
+    pub fn parts(&self) -> Vec<&str> {
+        let mut parts = vec![self.drive.as_str()];
+        parts.extend(self.components.iter().map(|x| x.as_str()));
+        parts
+    }
+
+    // splits a path to an executable into the `path`/`exec` pair expected by
+    // `HttpClient::post_title_launch`
+    pub fn launch_args(&self) -> GenericResult<(String, String)> {
+        match (self.parent(), self.file_name()) {
+            (Some(parent), Some(exec)) => Ok((parent.to_string(), String::from(exec))),
+            _ => Err(format!(
+                "Cannot launch '{}' because it does not name an executable.",
+                self
+            )
+            .into()),
+        }
+    }
+}
+
+fn validate_part(value: &str, kind: &str) -> GenericResult<()> {
+    if value.is_empty() {
+        return Err(format!("Invalid {}. Value must not be empty.", kind).into());
+    }
+    if value.contains(['\\', '/', ':']) {
+        return Err(format!(
+            "Invalid {} '{}'. Value must not contain '\\', '/' or ':'.",
+            kind, value
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas::{FileAttributes, FilebrowserEntry};
+use crate::utils::GenericResult;
+use futures::future::BoxFuture;
+use futures::stream::{FuturesUnordered, Stream, StreamExt};
+use log::debug;
+
+type WalkPredicate = Arc<dyn Fn(&WalkEntry) -> bool + Send + Sync>;
+type ListingFuture = BoxFuture<'static, (String, usize, GenericResult<Vec<FilebrowserEntry>>)>;
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WalkEntry {
+    pub path: String,
+    pub name: String,
+    pub depth: usize,
+    pub attributes: u32,
+    pub size: u32,
+}
+
+impl WalkEntry {
+    pub fn file_attributes(&self) -> FileAttributes {
+        FileAttributes::from_bits_retain(self.attributes)
+    }
+
+    pub fn is_directory(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::DIRECTORY)
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::HIDDEN)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::READ_ONLY)
+    }
+
+    pub fn is_system(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::SYSTEM)
+    }
+
+    pub fn is_archive(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::ARCHIVE)
+    }
+}
+
+// walks an Aurora path such as `Hdd1:\Games` over `/filebrowser`. entries
+// directly inside the root have a depth of 1.
+#[derive(Clone)]
+pub struct FilebrowserWalker {
+    client: HttpClient,
+    token: Option<String>,
+    root: String,
+    max_depth: Option<usize>,
+    concurrency: usize,
+    filter: Option<WalkPredicate>,
+    descend: Option<WalkPredicate>,
+}
+
+impl FilebrowserWalker {
+    pub fn new(client: HttpClient, token: Option<&str>, root: &str) -> Self {
+        Self {
+            client,
+            token: token.map(String::from),
+            root: String::from(root.trim_end_matches('\\')),
+            max_depth: None,
+            concurrency: 4,
+            filter: None,
+            descend: None,
+        }
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = std::cmp::max(concurrency, 1);
+        self
+    }
+
+    // only entries matching `filter` are yielded, directories are still
+    // walked whether they match or not
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&WalkEntry) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    // only directories matching `descend` are walked into
+    pub fn descend<F>(mut self, descend: F) -> Self
+    where
+        F: Fn(&WalkEntry) -> bool + Send + Sync + 'static,
+    {
+        self.descend = Some(Arc::new(descend));
+        self
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = GenericResult<WalkEntry>> {
+        let mut pending = VecDeque::new();
+        pending.push_back((self.root.clone(), 0));
+        let state = WalkState {
+            walker: self,
+            pending,
+            in_flight: FuturesUnordered::new(),
+            ready: VecDeque::new(),
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(item) = state.ready.pop_front() {
+                    return Some((item, state));
+                }
+                while state.in_flight.len() < state.walker.concurrency {
+                    match state.pending.pop_front() {
+                        Some((path, depth)) => {
+                            let listing = state.walker.list(path, depth);
+                            state.in_flight.push(listing);
+                        }
+                        None => break,
+                    }
+                }
+                let (path, depth, result) = state.in_flight.next().await?;
+                state.process(path, depth, result);
+            }
+        })
+    }
+
+    fn list(&self, path: String, depth: usize) -> ListingFuture {
+        let client = self.client.clone();
+        let token = self.token.clone();
+        Box::pin(async move {
+            debug!("Filebrowser Walker: Listing '{}'", path);
+            let result = client
+                .get_filebrowser(token.as_deref(), Some(&format!("{}\\", path)), None)
+                .await;
+            (path, depth, result)
+        })
+    }
+}
+
+struct WalkState {
+    walker: FilebrowserWalker,
+    pending: VecDeque<(String, usize)>,
+    in_flight: FuturesUnordered<ListingFuture>,
+    ready: VecDeque<GenericResult<WalkEntry>>,
+}
+
+impl WalkState {
+    fn process(
+        &mut self,
+        path: String,
+        depth: usize,
+        result: GenericResult<Vec<FilebrowserEntry>>,
+    ) {
+        let entries = match result {
+            Ok(x) => x,
+            Err(err) => {
+                self.ready.push_back(Err(format!(
+                    "Failed to list directory '{}'. Got the following error: {}",
+                    path, err
+                )
+                .into()));
+                return;
+            }
+        };
+        for entry in entries {
+            if entry.name == "." || entry.name == ".." {
+                continue;
+            }
+            let walk_entry = WalkEntry {
+                path: format!("{}\\{}", path, entry.name),
+                name: entry.name,
+                depth: depth + 1,
+                attributes: entry.attributes,
+                size: entry.size,
+            };
+            let below_max_depth = self.walker.max_depth.is_none_or(|x| walk_entry.depth < x);
+            if walk_entry.is_directory()
+                && below_max_depth
+                && self.walker.descend.as_ref().is_none_or(|x| x(&walk_entry))
+            {
+                self.pending
+                    .push_back((walk_entry.path.clone(), walk_entry.depth));
+            }
+            if self.walker.filter.as_ref().is_none_or(|x| x(&walk_entry)) {
+                self.ready.push_back(Ok(walk_entry));
+            }
+        }
+    }
+}
@@ -22,9 +22,30 @@
 use std::io::Write;
 use std::path::Path;
 
-use crate::utils::{create_parent_directories, GenericResult};
+use crate::aurora::assets::Asset;
+use crate::aurora::device_path::DevicePath;
+use crate::utils::{GenericError, GenericResult};
 use log::{debug, error, warn};
 
+pub const FATX_MAX_NAME_LENGTH: usize = 42;
+// besides ASCII letters and digits
+const FATX_ALLOWED_CHARACTERS: &str = " !#$%&'()-.@[]^_`{}~";
+
+mod async_client;
+pub mod find;
+pub mod pool;
+pub mod stream;
+pub mod sync;
+pub mod transfer;
+pub mod verify;
+pub use async_client::AsyncFtpClient;
+use find::{FindQuery, RemoteFind};
+use std::io::Read;
+use stream::{RemoteReader, RemoteWriter};
+use sync::{SyncOptions, SyncPlan};
+use transfer::{TransferOptions, TransferPlan};
+use verify::{Manifest, ManifestMismatch, Verification, VerifyOptions};
+
 #[derive(Clone, Debug)]
 pub struct FtpClient {
     ip: String,
@@ -115,6 +136,9 @@ impl FtpClient {
         result
     }
 
+    // an existing `local_dir` is kept and downloaded into. always starts
+    // every file over and deletes partial files when it fails, use
+    // `download_directory_with_options` to resume
     pub fn download_directory(
         &self,
         ftp_stream: Option<suppaftp::FtpStream>,
@@ -133,6 +157,8 @@ impl FtpClient {
         result
     }
 
+    // always starts over and deletes the partial file when it fails, use
+    // `download_file_with_options` to resume
     pub fn download_file(
         &self,
         ftp_stream: Option<suppaftp::FtpStream>,
@@ -168,6 +194,130 @@ impl FtpClient {
         result
     }
 
+    // lazily searches below `remote_dir`, see `find::RemoteFind`
+    pub fn find(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_dir: &str,
+        query: FindQuery,
+    ) -> GenericResult<RemoteFind> {
+        let quit_stream = ftp_stream.is_none();
+        let stream = match ftp_stream {
+            Some(x) => x,
+            None => self.new_ftp_stream()?,
+        };
+        Ok(RemoteFind::new(stream, quit_stream, remote_dir, query))
+    }
+
+    pub fn open_read(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+    ) -> GenericResult<RemoteReader> {
+        let quit_stream = ftp_stream.is_none();
+        let stream = match ftp_stream {
+            Some(x) => x,
+            None => self.new_ftp_stream()?,
+        };
+        RemoteReader::open(stream, quit_stream, remote_file)
+    }
+
+    pub fn open_write(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+    ) -> GenericResult<RemoteWriter> {
+        let quit_stream = ftp_stream.is_none();
+        let stream = match ftp_stream {
+            Some(x) => x,
+            None => self.new_ftp_stream()?,
+        };
+        RemoteWriter::open(stream, quit_stream, remote_file)
+    }
+
+    pub fn download_to_vec(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+    ) -> GenericResult<Vec<u8>> {
+        let mut reader = self.open_read(ftp_stream, remote_file)?;
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        reader.finish()?;
+        Ok(buffer)
+    }
+
+    pub fn upload_from_slice(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        data: &[u8],
+        remote_file: &str,
+    ) -> GenericResult<()> {
+        let mut writer = self.open_write(ftp_stream, remote_file)?;
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    pub fn download_asset(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+    ) -> GenericResult<Asset> {
+        Asset::from_be_bytes(&self.download_to_vec(ftp_stream, remote_file)?)
+    }
+
+    pub fn upload_asset(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        asset: &Asset,
+        remote_file: &str,
+    ) -> GenericResult<()> {
+        self.upload_from_slice(ftp_stream, &asset.to_be_bytes(), remote_file)
+    }
+
+    pub fn create_manifest(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_dir: &str,
+    ) -> GenericResult<Manifest> {
+        let mut manifest = None;
+        self.transfer(ftp_stream, |stream| {
+            manifest = Some(Manifest::from_remote_dir(stream, remote_dir)?);
+            Ok(())
+        })?;
+        manifest.ok_or_else(|| "Failed to create manifest.".into())
+    }
+
+    pub fn verify_manifest(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        manifest: &Manifest,
+        remote_dir: &str,
+        hash: bool,
+    ) -> GenericResult<Vec<ManifestMismatch>> {
+        let mut mismatches = None;
+        self.transfer(ftp_stream, |stream| {
+            mismatches = Some(manifest.verify_remote_dir(stream, remote_dir, hash)?);
+            Ok(())
+        })?;
+        mismatches.ok_or_else(|| "Failed to verify manifest.".into())
+    }
+
+    pub fn verify_file(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_file: &Path,
+        remote_file: &str,
+        options: &VerifyOptions,
+    ) -> GenericResult<Verification> {
+        let mut verification = None;
+        self.transfer(ftp_stream, |stream| {
+            verification = Some(verify::verify(stream, local_file, remote_file, options)?);
+            Ok(())
+        })?;
+        verification.ok_or_else(|| "Failed to verify file.".into())
+    }
+
     pub fn list_entry(
         &self,
         ftp_stream: Option<suppaftp::FtpStream>,
@@ -238,26 +388,268 @@ impl FtpClient {
         }
         result
     }
+
+    pub fn download_directory_with_options(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_dir: &str,
+        local_dir: &Path,
+        options: &TransferOptions,
+    ) -> GenericResult<()> {
+        // same semantics as `download_directory`, nothing is removed from
+        // `local_dir` and files are only replaced once fully downloaded
+        if local_dir.is_file() {
+            return Err("Destination already exists and is a file.".into());
+        }
+        self.transfer(ftp_stream, |stream| {
+            let mut plan = TransferPlan::default();
+            transfer::plan_download_directory(stream, remote_dir, local_dir, &mut plan)?;
+            transfer::download(stream, &plan, options)
+        })
+    }
+
+    pub fn download_file_with_options(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+        local_file: &Path,
+        options: &TransferOptions,
+    ) -> GenericResult<()> {
+        self.transfer(ftp_stream, |stream| {
+            let plan = transfer::plan_download_file(stream, remote_file, local_file)?;
+            transfer::download(stream, &plan, options)
+        })
+    }
+
+    // downloads `remote_file` and checks it against the remote side, the
+    // local file is only replaced when the download is complete
+    pub fn download_file_verified(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        remote_file: &str,
+        local_file: &Path,
+        verify: &VerifyOptions,
+    ) -> GenericResult<()> {
+        let options = TransferOptions::new().verify(verify.clone());
+        self.download_file_with_options(ftp_stream, remote_file, local_file, &options)
+    }
+
+    pub fn upload_directory_with_options(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_dir: &Path,
+        remote_dir: &str,
+        options: &TransferOptions,
+    ) -> GenericResult<()> {
+        self.transfer(ftp_stream, |stream| {
+            let mut plan = TransferPlan::default();
+            transfer::plan_upload_directory(local_dir, remote_dir, &mut plan)?;
+            transfer::upload(stream, &plan, options)
+        })
+    }
+
+    pub fn upload_file_with_options(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_file: &Path,
+        remote_file: &str,
+        options: &TransferOptions,
+    ) -> GenericResult<()> {
+        self.transfer(ftp_stream, |stream| {
+            let plan = transfer::plan_upload_file(local_file, remote_file)?;
+            transfer::upload(stream, &plan, options)
+        })
+    }
+
+    // uploads `local_file` and checks it against the remote side
+    pub fn upload_file_verified(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_file: &Path,
+        remote_file: &str,
+        verify: &VerifyOptions,
+    ) -> GenericResult<()> {
+        let options = TransferOptions::new().verify(verify.clone());
+        self.upload_file_with_options(ftp_stream, local_file, remote_file, &options)
+    }
+
+    // compares `local_dir` with `remote_dir` without changing either side
+    pub fn plan_sync(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_dir: &Path,
+        remote_dir: &str,
+        options: &SyncOptions,
+    ) -> GenericResult<SyncPlan> {
+        let mut plan = None;
+        self.transfer(ftp_stream, |stream| {
+            plan = Some(sync::plan(stream, local_dir, remote_dir, options)?);
+            Ok(())
+        })?;
+        plan.ok_or_else(|| "Failed to plan sync.".into())
+    }
+
+    // plans and applies a sync, local data is never removed unless
+    // `delete_extraneous` is set. returns the plan that was applied, or that
+    // would have been applied for a dry run.
+    pub fn sync(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        local_dir: &Path,
+        remote_dir: &str,
+        options: &SyncOptions,
+    ) -> GenericResult<SyncPlan> {
+        let mut plan = None;
+        self.transfer(ftp_stream, |stream| {
+            let new_plan = sync::plan(stream, local_dir, remote_dir, options)?;
+            if !options.dry_run {
+                sync::execute(stream, &new_plan, options)?;
+            }
+            plan = Some(new_plan);
+            Ok(())
+        })?;
+        plan.ok_or_else(|| "Failed to plan sync.".into())
+    }
+
+    // `options` should be the ones the plan was made with, only their
+    // transfer options and remote time zone are used here
+    pub fn execute_sync_plan(
+        &self,
+        ftp_stream: Option<suppaftp::FtpStream>,
+        plan: &SyncPlan,
+        options: &SyncOptions,
+    ) -> GenericResult<()> {
+        self.transfer(ftp_stream, |stream| sync::execute(stream, plan, options))
+    }
+
+    // after a failed or cancelled transfer the control connection may still
+    // have unread replies, so errors while quitting are ignored in that case
+    fn transfer<F>(&self, ftp_stream: Option<suppaftp::FtpStream>, f: F) -> GenericResult<()>
+    where
+        F: FnOnce(&mut suppaftp::FtpStream) -> GenericResult<()>,
+    {
+        let quit_stream = ftp_stream.is_none();
+        let mut stream = match ftp_stream {
+            Some(x) => x,
+            None => self.new_ftp_stream()?,
+        };
+        let result = f(&mut stream);
+        if quit_stream {
+            match result {
+                Ok(_) => stream.quit()?,
+                Err(_) => {
+                    let _ = stream.quit();
+                }
+            }
+        }
+        result
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct FTPPath {
     pub parts: Vec<String>,
 }
 
+impl From<&DevicePath> for FTPPath {
+    fn from(device_path: &DevicePath) -> Self {
+        Self::from_device_path(device_path)
+    }
+}
+
+impl TryFrom<&FTPPath> for DevicePath {
+    type Error = GenericError;
+
+    fn try_from(ftp_path: &FTPPath) -> GenericResult<Self> {
+        ftp_path.to_device_path()
+    }
+}
+
 impl FTPPath {
     pub fn new() -> Self {
         Self { parts: Vec::new() }
     }
 
+    // `.` components are dropped and `..` removes the previous component,
+    // paths cannot go above the root
     pub fn from_str(path: &str) -> Self {
-        let mut parts: Vec<String> = Vec::new();
-        for x in path.split("/").into_iter() {
-            if x != "" {
-                parts.push(String::from(x));
+        let mut ftp_path = Self::new();
+        ftp_path.push_normalized(path);
+        ftp_path
+    }
+
+    // the first component of an FTP path is the drive, for example
+    // `/Hdd1/Content` is `Hdd1:\Content`
+    pub fn from_device_path(device_path: &DevicePath) -> Self {
+        let mut parts = vec![device_path.drive.clone()];
+        parts.extend(device_path.components.iter().cloned());
+        Self { parts }
+    }
+
+    pub fn to_device_path(&self) -> GenericResult<DevicePath> {
+        match self.parts.split_first() {
+            Some((drive, components)) => {
+                let components: Vec<&str> = components.iter().map(|x| x.as_str()).collect();
+                DevicePath::from_parts(drive, &components)
+            }
+            None => {
+                error!("FTPPath: The root path '/' has no device path.");
+                Err("The root path '/' has no device path.".into())
             }
         }
-        Self { parts }
+    }
+
+    // appends the components of the relative path `path`
+    pub fn join(&self, path: &str) -> Self {
+        let mut ftp_path = self.clone();
+        ftp_path.push_normalized(path);
+        ftp_path
+    }
+
+    fn push_normalized(&mut self, path: &str) {
+        for x in path.split('/') {
+            match x {
+                "" | "." => (),
+                ".." => {
+                    self.parts.pop();
+                }
+                _ => self.parts.push(String::from(x)),
+            }
+        }
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.parts.is_empty()
+    }
+
+    // FATX names are case insensitive
+    pub fn eq_ignore_case(&self, other: &FTPPath) -> bool {
+        self.parts.len() == other.parts.len()
+            && self
+                .parts
+                .iter()
+                .zip(other.parts.iter())
+                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+    }
+
+    pub fn starts_with_ignore_case(&self, other: &FTPPath) -> bool {
+        self.parts.len() >= other.parts.len()
+            && self
+                .parts
+                .iter()
+                .zip(other.parts.iter())
+                .all(|(a, b)| a.eq_ignore_ascii_case(b))
+    }
+
+    pub fn validate_fatx(&self) -> GenericResult<()> {
+        for part in &self.parts {
+            validate_fatx_name(part)?;
+        }
+        Ok(())
+    }
+
+    pub fn is_fatx_valid(&self) -> bool {
+        self.parts.iter().all(|x| fatx_name_error(x).is_none())
     }
 
     pub fn from_vec(path_parts: &Vec<String>) -> Self {
@@ -301,6 +693,42 @@ impl FTPPath {
     }
 }
 
+pub fn validate_fatx_name(name: &str) -> GenericResult<()> {
+    match fatx_name_error(name) {
+        Some(msg) => {
+            error!("{}", msg);
+            Err(msg.into())
+        }
+        None => Ok(()),
+    }
+}
+
+// names on FATX devices are limited to 42 characters from the set that FAT
+// allows in short names, which excludes `+ , ; =` unlike long FAT names
+fn fatx_name_error(name: &str) -> Option<String> {
+    if name.is_empty() {
+        Some(String::from("FATX names must not be empty."))
+    } else if name == "." || name == ".." {
+        Some(format!("'{}' is not a valid FATX name.", name))
+    } else if name.len() > FATX_MAX_NAME_LENGTH {
+        Some(format!(
+            "FATX name '{}' is {} characters long but at most {} are allowed.",
+            name,
+            name.len(),
+            FATX_MAX_NAME_LENGTH
+        ))
+    } else {
+        name.chars()
+            .find(|x| !x.is_ascii_alphanumeric() && !FATX_ALLOWED_CHARACTERS.contains(*x))
+            .map(|x| {
+                format!(
+                    "FATX name '{}' contains the invalid character {:?}.",
+                    name, x
+                )
+            })
+    }
+}
+
 fn create_directory(ftp_stream: &mut suppaftp::FtpStream, remote_dir: &str) -> GenericResult<()> {
     debug!("Create Directory: '{}'", remote_dir);
     let remote_path = FTPPath::from_str(remote_dir);
@@ -335,26 +763,19 @@ fn create_directory(ftp_stream: &mut suppaftp::FtpStream, remote_dir: &str) -> G
 
 fn delete_directory(ftp_stream: &mut suppaftp::FtpStream, remote_dir: &str) -> GenericResult<()> {
     debug!("Delete Directory: '{}'", remote_dir);
-    let mut remote_path = FTPPath::from_str(remote_dir);
-    for entry in list_directory_contents(ftp_stream, &remote_path.to_string())? {
-        remote_path.push(entry.name());
-        let new_remote_path = remote_path.to_string();
-        remote_path.pop();
-        if entry.is_file() {
-            delete_file(ftp_stream, &new_remote_path)?;
-        }
-        if entry.is_directory() {
-            delete_directory(ftp_stream, &new_remote_path)?;
-        }
+    let mut walk = transfer::RemoteWalk::new(remote_dir);
+    while let Some((relative, remote)) = walk.next_directory() {
+        walk.add_entries(&relative, list_directory_contents(ftp_stream, &remote)?);
     }
-    debug!(
-        "Delete Directory: Calling rmdir('{}')",
-        remote_path.to_string()
-    );
-    match ftp_stream.rmdir(remote_path.to_string()) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(e.into()),
+    let (files, directories) = walk.delete_order();
+    for file in files {
+        delete_file(ftp_stream, &file)?;
     }
+    for directory in directories {
+        debug!("Delete Directory: Calling rmdir('{}')", directory);
+        ftp_stream.rmdir(&directory)?;
+    }
+    Ok(())
 }
 
 fn delete_file(ftp_stream: &mut suppaftp::FtpStream, remote_file: &str) -> GenericResult<()> {
@@ -374,31 +795,16 @@ fn download_directory(
     remote_dir: &str,
     local_path: &Path,
 ) -> GenericResult<()> {
-    // mirror contents of `remote_path` into `local_path`
-    // if a directory already exists at `local_path`, it will be
-    // deleted before remote files are downloaded into it.
+    // mirror contents of `remote_path` into `local_path`. files are
+    // downloaded next to their destination and moved into place once
+    // complete, local files that are not on the console are kept.
     debug!("Download Directory: '{}'", remote_dir);
-    std::fs::create_dir_all(local_path)?;
     if local_path.is_file() {
         return Err("Destination already exists and is a file.".into());
     }
-    if local_path.is_dir() {
-        std::fs::remove_dir_all(local_path)?;
-    }
-    let mut remote_path = FTPPath::from_str(remote_dir);
-    for entry in list_directory_contents(ftp_stream, &remote_path.to_string())? {
-        let new_local_path = local_path.join(entry.name());
-        remote_path.push(entry.name());
-        let new_remote_path = remote_path.to_string();
-        remote_path.pop();
-        if entry.is_directory() {
-            download_directory(ftp_stream, &new_remote_path, &new_local_path)?;
-        }
-        if entry.is_file() {
-            download_file(ftp_stream, &new_remote_path, &new_local_path)?;
-        }
-    }
-    Ok(())
+    let mut plan = TransferPlan::default();
+    transfer::plan_download_directory(ftp_stream, remote_dir, local_path, &mut plan)?;
+    transfer::download(ftp_stream, &plan, &TransferOptions::new())
 }
 
 fn download_file(
@@ -406,42 +812,10 @@ fn download_file(
     remote_path: &str,
     local_path: &Path,
 ) -> GenericResult<()> {
+    // an existing `local_path` is only replaced once the download is complete
     debug!("Download File: '{}'", remote_path);
-    // prepare local directory that file will be downloaded into
-    create_parent_directories(local_path)?;
-    if local_path.is_file() {
-        std::fs::remove_file(local_path)?;
-    }
-    if local_path.is_dir() {
-        return Err("Destination already exists and is a directory.".into());
-    }
-    let mut file = std::fs::OpenOptions::new()
-        .create_new(true)
-        .append(true)
-        .open(local_path)?;
-    debug!(
-        "Download File: calling retr('{}')",
-        FTPPath::from_str(remote_path).to_string()
-    );
-    ftp_stream.retr(&FTPPath::from_str(remote_path).to_string(), |stream| {
-        let mut buf: Vec<u8> = vec![0; 4096];
-        loop {
-            let bytes_read = match stream.read(&mut buf) {
-                Ok(x) => x,
-                Err(e) => return Err(suppaftp::FtpError::ConnectionError(e)),
-            };
-            debug!("Download File: Read {} bytes", bytes_read);
-            if bytes_read == 0 {
-                break;
-            }
-            match file.write_all(&buf[0..bytes_read]) {
-                Ok(_) => (),
-                Err(e) => return Err(suppaftp::FtpError::ConnectionError(e)),
-            };
-        }
-        Ok(())
-    })?;
-    Ok(())
+    let plan = transfer::plan_download_file(ftp_stream, remote_path, local_path)?;
+    transfer::download(ftp_stream, &plan, &TransferOptions::new())
 }
 
 fn list_directory_contents(
@@ -558,44 +932,9 @@ fn upload_directory(
     remote_dir: &str,
 ) -> GenericResult<()> {
     debug!("Upload Directory: '{}'", remote_dir);
-    create_directory(ftp_stream, remote_dir)?;
-    let mut new_remote_path = FTPPath::from_str(remote_dir);
-    for entry in std::fs::read_dir(local_dir)? {
-        let path = entry?.path();
-        let file_name = match path.file_name() {
-            None => {
-                error!(
-                    "Upload Directory: Failed to get file name for path '{}'",
-                    path.display()
-                );
-                return Err(
-                    format!("Failed to get file name for path '{}'.", path.display()).into(),
-                );
-            }
-            Some(x) => match x.to_str() {
-                Some(y) => y,
-                None => {
-                    error!(
-                        "Upload Directory: Failed to convert file name of path '{}' to str",
-                        path.display()
-                    );
-                    return Err(format!(
-                        "Failed to convert file name of path '{}' to str.",
-                        path.display()
-                    )
-                    .into());
-                }
-            },
-        };
-        new_remote_path.push(file_name);
-        if path.is_dir() {
-            upload_directory(ftp_stream, &path, &new_remote_path.to_string())?;
-        } else {
-            upload_file(ftp_stream, &path, &new_remote_path.to_string())?;
-        }
-        new_remote_path.pop();
-    }
-    Ok(())
+    let mut plan = TransferPlan::default();
+    transfer::plan_upload_directory(local_dir, remote_dir, &mut plan)?;
+    transfer::upload(ftp_stream, &plan, &TransferOptions::new())
 }
 
 fn upload_file(
@@ -632,3 +971,32 @@ fn upload_file(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fatx_names() {
+        for name in ["Content", "Save Game (1).dat", "[x]{y}~#$%&'@^_`!-", "a.b"] {
+            assert!(validate_fatx_name(name).is_ok(), "{}", name);
+        }
+        for name in [
+            "",
+            ".",
+            "..",
+            "a+b",
+            "a,b",
+            "a;b",
+            "a=b",
+            "a:b",
+            "a\\b",
+            "é",
+            &"a".repeat(43),
+        ] {
+            assert!(validate_fatx_name(name).is_err(), "{}", name);
+        }
+        assert!(FTPPath::from_str("/Hdd1/Content/0000000000000000").is_fatx_valid());
+        assert!(!FTPPath::from_str("/Hdd1/a=b").is_fatx_valid());
+    }
+}
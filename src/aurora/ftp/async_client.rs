@@ -0,0 +1,553 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+// TODO improve logging
+use std::path::Path;
+
+use super::transfer::{self, RemoteWalk, TransferPlan};
+use super::FTPPath;
+use crate::utils::GenericResult;
+use log::{debug, error, warn};
+use suppaftp::tokio::AsyncFtpStream;
+
+// async counterpart of `FtpClient`. each operation opens its own connection
+// unless a stream is passed in, which is then consumed by the operation.
+#[derive(Clone, Debug)]
+pub struct AsyncFtpClient {
+    ip: String,
+    port: usize,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl AsyncFtpClient {
+    pub fn new(
+        ip: String,
+        port: usize,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Self {
+        Self {
+            ip,
+            port,
+            username,
+            password,
+        }
+    }
+
+    pub async fn new_ftp_stream(&self) -> GenericResult<AsyncFtpStream> {
+        let address = format!("{}:{}", self.ip, self.port);
+        debug!("Create Async FTP Stream: calling connect('{}')", address);
+        match AsyncFtpStream::connect(address).await {
+            Ok(mut ftp_stream) => {
+                if let (Some(username), Some(password)) = (&self.username, &self.password) {
+                    ftp_stream.login(username, password).await?;
+                }
+                ftp_stream
+                    .transfer_type(suppaftp::types::FileType::Binary)
+                    .await?;
+                Ok(ftp_stream)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn stream(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+    ) -> GenericResult<(AsyncFtpStream, bool)> {
+        match ftp_stream {
+            Some(x) => Ok((x, false)),
+            None => Ok((self.new_ftp_stream().await?, true)),
+        }
+    }
+
+    pub async fn create_directory(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_dir: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = create_directory(&mut stream, remote_dir).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn delete_directory(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_dir: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = delete_directory(&mut stream, remote_dir).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn delete_file(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_file: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = delete_file(&mut stream, remote_file).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn download_directory(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_dir: &str,
+        local_dir: &Path,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = download_directory(&mut stream, remote_dir, local_dir).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn download_file(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_file: &str,
+        local_file: &Path,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = download_file(&mut stream, remote_file, local_file).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn list_directory_contents(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_dir: &str,
+    ) -> GenericResult<Vec<suppaftp::list::File>> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = list_directory_contents(&mut stream, remote_dir).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn list_entry(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_path: &str,
+    ) -> GenericResult<Option<suppaftp::list::File>> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = list_entry(&mut stream, remote_path).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn rename(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        remote_path: &str,
+        new_name: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = rename(&mut stream, remote_path, new_name).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn upload_directory(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        local_dir: &Path,
+        remote_dir: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = upload_directory(&mut stream, local_dir, remote_dir).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+
+    pub async fn upload_file(
+        &self,
+        ftp_stream: Option<AsyncFtpStream>,
+        local_file: &Path,
+        remote_file: &str,
+    ) -> GenericResult<()> {
+        let (mut stream, quit_stream) = self.stream(ftp_stream).await?;
+        let result = upload_file(&mut stream, local_file, remote_file).await;
+        if quit_stream {
+            stream.quit().await?;
+        }
+        result
+    }
+}
+
+impl From<&super::FtpClient> for AsyncFtpClient {
+    fn from(client: &super::FtpClient) -> Self {
+        Self::new(
+            client.ip.clone(),
+            client.port,
+            client.username.clone(),
+            client.password.clone(),
+        )
+    }
+}
+
+async fn create_directory(ftp_stream: &mut AsyncFtpStream, remote_dir: &str) -> GenericResult<()> {
+    debug!("Create Directory: '{}'", remote_dir);
+    let remote_path = FTPPath::from_str(remote_dir);
+    let mut new_remote_path = FTPPath::new();
+    for directory in remote_path.parts() {
+        new_remote_path.push(&directory);
+        match list_entry(ftp_stream, &new_remote_path.to_string()).await? {
+            Some(x) => {
+                // entry already exists, error if it is not a directory
+                if !x.is_directory() {
+                    warn!(
+                        "Create Directory: Cannot create directory '{}' because the path '{}' exists and is not a directory.",
+                        remote_path.to_string(), new_remote_path.to_string()
+                    );
+                    return Err(format!(
+                        "Cannot create directory '{}' because the path '{}' exists and is not a directory.",
+                        remote_path.to_string(), new_remote_path.to_string()
+                    ).into());
+                }
+            }
+            None => {
+                debug!(
+                    "Create Directory: Calling mkdir('{}')",
+                    new_remote_path.to_string()
+                );
+                ftp_stream.mkdir(new_remote_path.to_string()).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// lists every directory below `remote_dir`, the same walk the blocking
+// client does
+async fn walk_directory(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_dir: &str,
+) -> GenericResult<RemoteWalk> {
+    let mut walk = RemoteWalk::new(remote_dir);
+    while let Some((relative, remote)) = walk.next_directory() {
+        let entries = list_directory_contents(ftp_stream, &remote).await?;
+        walk.add_entries(&relative, entries);
+    }
+    Ok(walk)
+}
+
+async fn delete_directory(ftp_stream: &mut AsyncFtpStream, remote_dir: &str) -> GenericResult<()> {
+    debug!("Delete Directory: '{}'", remote_dir);
+    let (files, directories) = walk_directory(ftp_stream, remote_dir).await?.delete_order();
+    for file in files {
+        delete_file(ftp_stream, &file).await?;
+    }
+    for directory in directories {
+        debug!("Delete Directory: Calling rmdir('{}')", directory);
+        ftp_stream.rmdir(&directory).await?;
+    }
+    Ok(())
+}
+
+async fn delete_file(ftp_stream: &mut AsyncFtpStream, remote_file: &str) -> GenericResult<()> {
+    debug!("Delete File: '{}'", remote_file);
+    debug!(
+        "Delete File: Calling rm('{}')",
+        FTPPath::from_str(remote_file).to_string()
+    );
+    match ftp_stream
+        .rm(FTPPath::from_str(remote_file).to_string())
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn download_directory(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_dir: &str,
+    local_path: &Path,
+) -> GenericResult<()> {
+    // mirror contents of `remote_path` into `local_path`. local files that
+    // are not on the console are kept.
+    debug!("Download Directory: '{}'", remote_dir);
+    if is_file(local_path).await {
+        return Err("Destination already exists and is a file.".into());
+    }
+    let mut plan = TransferPlan::default();
+    walk_directory(ftp_stream, remote_dir)
+        .await?
+        .add_to_download_plan(local_path, &mut plan);
+    for directory in &plan.local_directories {
+        tokio::fs::create_dir_all(directory).await?;
+    }
+    for item in &plan.files {
+        download_file(ftp_stream, &item.remote, &item.local).await?;
+    }
+    Ok(())
+}
+
+async fn download_file(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_path: &str,
+    local_path: &Path,
+) -> GenericResult<()> {
+    // the file is downloaded next to `local_path` and only replaces it
+    // once complete
+    debug!("Download File: '{}'", remote_path);
+    if let Some(parent) = local_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    if tokio::fs::metadata(local_path)
+        .await
+        .is_ok_and(|x| x.is_dir())
+    {
+        return Err("Destination already exists and is a directory.".into());
+    }
+    let part_file = transfer::part_path(local_path);
+    if let Err(err) = download_part(ftp_stream, remote_path, &part_file).await {
+        if is_file(&part_file).await {
+            tokio::fs::remove_file(&part_file).await?;
+        }
+        return Err(err);
+    }
+    if is_file(local_path).await {
+        tokio::fs::remove_file(local_path).await?;
+    }
+    tokio::fs::rename(&part_file, local_path).await?;
+    Ok(())
+}
+
+async fn is_file(path: &Path) -> bool {
+    tokio::fs::metadata(path).await.is_ok_and(|x| x.is_file())
+}
+
+async fn download_part(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_path: &str,
+    part_file: &Path,
+) -> GenericResult<()> {
+    let mut file = tokio::fs::File::create(part_file).await?;
+    debug!(
+        "Download File: calling retr_as_stream('{}')",
+        FTPPath::from_str(remote_path).to_string()
+    );
+    let mut data_stream = ftp_stream
+        .retr_as_stream(FTPPath::from_str(remote_path).to_string())
+        .await?;
+    let bytes_read = tokio::io::copy(&mut data_stream, &mut file).await?;
+    debug!("Download File: Read {} bytes", bytes_read);
+    ftp_stream.finalize_retr_stream(data_stream).await?;
+    tokio::io::AsyncWriteExt::flush(&mut file).await?;
+    file.sync_all().await?;
+    Ok(())
+}
+
+async fn list_directory_contents(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_path: &str,
+) -> GenericResult<Vec<suppaftp::list::File>> {
+    debug!("List Directory Contents: '{}'", remote_path);
+    // `list(<path>)` returns the contents of the CWD instead of
+    // the contents of `<path>`. so set the CWD to the parent of
+    // `<path>`, then list the contents of the CWD, then restore
+    // the original CWD
+    let original_cwd = ftp_stream.pwd().await?;
+    debug!(
+        "List Directory Contents: calling cwd('{}')",
+        FTPPath::from_str(remote_path).to_string()
+    );
+    ftp_stream
+        .cwd(FTPPath::from_str(remote_path).to_string())
+        .await?;
+    let mut contents: Vec<suppaftp::list::File> = Vec::new();
+    debug!("List Directory Contents: calling list(None)");
+    let entries = match ftp_stream.list(None).await {
+        Ok(x) => x,
+        Err(e) => {
+            error!(
+                "List Directory Contents: Failed to list cwd. Got the error: '{}'.",
+                e
+            );
+            // restore orginal cwd
+            debug!("List Directory Contents: calling cwd('{}')", original_cwd);
+            ftp_stream.cwd(original_cwd).await?;
+            return Err(e.into());
+        }
+    };
+    for entry in entries {
+        contents.push(suppaftp::list::ListParser::parse_posix(&entry)?);
+    }
+    // restore original cwd
+    debug!("List Directory Contents: calling cwd('{}')", original_cwd);
+    ftp_stream.cwd(original_cwd).await?;
+    Ok(contents)
+}
+
+async fn list_entry(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_path: &str,
+) -> GenericResult<Option<suppaftp::list::File>> {
+    debug!("List Entry: '{}'", remote_path);
+    let ftp_path = FTPPath::from_str(remote_path);
+    let parent_path = match ftp_path.parent_string() {
+        Some(x) => x,
+        None => {
+            error!(
+                "List Entry: Cannot list entry at path '{}' because parent is 'None'.",
+                ftp_path.to_string()
+            );
+            return Err(format!(
+                "Cannot list entry at path '{}' because parent is 'None'.",
+                ftp_path.to_string()
+            )
+            .into());
+        }
+    };
+    let file_name = match ftp_path.file_name() {
+        Some(x) => x,
+        None => {
+            error!(
+                "List Entry: Cannot list entry at path '{}' because file name is 'None'.",
+                ftp_path.to_string()
+            );
+            return Err(format!(
+                "Cannot list entry at path '{}' because file name is 'None'.",
+                ftp_path.to_string()
+            )
+            .into());
+        }
+    };
+    for entry in list_directory_contents(ftp_stream, &parent_path).await? {
+        if entry.name() == file_name {
+            debug!(
+                "List Entry: Found entry at '{}/{}'",
+                &parent_path,
+                entry.name()
+            );
+            return Ok(Some(entry));
+        }
+    }
+    debug!("List Entry: Did not find entry for path '{}'", remote_path);
+    Ok(None)
+}
+
+async fn rename(
+    ftp_stream: &mut AsyncFtpStream,
+    remote_path: &str,
+    new_name: &str,
+) -> GenericResult<()> {
+    debug!("Rename: '{}' to '{}'", remote_path, new_name);
+    let src_path = FTPPath::from_str(remote_path);
+    let mut dest_path = FTPPath::from_str(remote_path);
+    dest_path.pop();
+    dest_path.push(new_name);
+    debug!(
+        "Rename: calling rename('{}', '{}')",
+        src_path.to_string(),
+        dest_path.to_string()
+    );
+    match ftp_stream
+        .rename(src_path.to_string(), dest_path.to_string())
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn upload_directory(
+    ftp_stream: &mut AsyncFtpStream,
+    local_dir: &Path,
+    remote_dir: &str,
+) -> GenericResult<()> {
+    debug!("Upload Directory: '{}'", remote_dir);
+    // the local walk uses std::fs, so it runs off the async workers
+    let (local_dir, remote_dir) = (local_dir.to_path_buf(), remote_dir.to_string());
+    let plan = match tokio::task::spawn_blocking(move || {
+        let mut plan = TransferPlan::default();
+        transfer::plan_upload_directory(&local_dir, &remote_dir, &mut plan).map(|_| plan)
+    })
+    .await
+    {
+        Ok(x) => x?,
+        Err(err) => return Err(err.into()),
+    };
+    for directory in &plan.remote_directories {
+        create_directory(ftp_stream, directory).await?;
+    }
+    for item in &plan.files {
+        upload_file(ftp_stream, &item.local, &item.remote).await?;
+    }
+    Ok(())
+}
+
+async fn upload_file(
+    ftp_stream: &mut AsyncFtpStream,
+    local_path: &Path,
+    remote_file: &str,
+) -> GenericResult<()> {
+    debug!("Upload File: '{}'", remote_file);
+    let remote_path = FTPPath::from_str(remote_file);
+    let file_len_bytes = tokio::fs::metadata(local_path).await?.len();
+    let mut file = tokio::fs::File::open(local_path).await?;
+    if let Some(x) = remote_path.parent_string() {
+        create_directory(ftp_stream, &x).await?;
+    }
+    debug!(
+        "Upload File: calling put_file('{}')",
+        remote_path.to_string()
+    );
+    let bytes_written = ftp_stream
+        .put_file(remote_path.to_string(), &mut file)
+        .await?;
+    if bytes_written != file_len_bytes {
+        error!(
+            "Upload File: Expected to write {} bytes to remote but wrote {}.",
+            file_len_bytes, bytes_written
+        );
+        return Err(format!(
+            "Expected to write {} bytes to remote but wrote {}.",
+            file_len_bytes, bytes_written
+        )
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,380 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use super::FTPPath;
+use crate::utils::GenericResult;
+use log::debug;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EntryType {
+    File,
+    Directory,
+}
+
+// a glob matched against the path of an entry relative to the search root.
+// `*` matches any characters within a component, `?` matches a single
+// character and a `**` component matches any number of components.
+#[derive(Clone, Debug)]
+pub struct GlobPattern {
+    parts: Vec<String>,
+    case_sensitive: bool,
+}
+
+impl GlobPattern {
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            parts: pattern
+                .split('/')
+                .filter(|x| !x.is_empty())
+                .map(String::from)
+                .collect(),
+            case_sensitive: false,
+        }
+    }
+
+    // FATX names are case insensitive, so patterns are too by default
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    pub fn is_match(&self, relative: &[String]) -> bool {
+        self.match_parts(&self.parts, relative, false)
+    }
+
+    // whether a path below `relative` could still match
+    fn could_match_below(&self, relative: &[String]) -> bool {
+        self.match_parts(&self.parts, relative, true)
+    }
+
+    fn match_parts(&self, pattern: &[String], path: &[String], below: bool) -> bool {
+        let (first, rest) = match pattern.split_first() {
+            Some(x) => x,
+            None => return path.is_empty() && !below,
+        };
+        if first == "**" {
+            return self.match_parts(rest, path, below)
+                || (!path.is_empty() && self.match_parts(pattern, &path[1..], below));
+        }
+        match path.split_first() {
+            Some((name, path)) => {
+                self.match_component(first, name) && self.match_parts(rest, path, below)
+            }
+            None => below,
+        }
+    }
+
+    fn match_component(&self, pattern: &str, name: &str) -> bool {
+        let (pattern, name): (Vec<char>, Vec<char>) = match self.case_sensitive {
+            true => (pattern.chars().collect(), name.chars().collect()),
+            false => (
+                pattern.to_lowercase().chars().collect(),
+                name.to_lowercase().chars().collect(),
+            ),
+        };
+        // iterative wildcard matching that backtracks to the last `*`
+        let (mut p, mut n) = (0, 0);
+        let mut star: Option<(usize, usize)> = None;
+        while n < name.len() {
+            if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+                p += 1;
+                n += 1;
+            } else if p < pattern.len() && pattern[p] == '*' {
+                star = Some((p, n));
+                p += 1;
+            } else if let Some((star_p, star_n)) = star {
+                p = star_p + 1;
+                n = star_n + 1;
+                star = Some((star_p, star_n + 1));
+            } else {
+                return false;
+            }
+        }
+        pattern[p..].iter().all(|x| *x == '*')
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FindQuery {
+    pattern: Option<GlobPattern>,
+    entry_type: Option<EntryType>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    modified_after: Option<SystemTime>,
+    modified_before: Option<SystemTime>,
+    max_depth: Option<usize>,
+}
+
+impl FindQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pattern(mut self, pattern: GlobPattern) -> Self {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn glob(self, pattern: &str) -> Self {
+        self.pattern(GlobPattern::new(pattern))
+    }
+
+    pub fn entry_type(mut self, entry_type: EntryType) -> Self {
+        self.entry_type = Some(entry_type);
+        self
+    }
+
+    // size filters only match files
+    pub fn min_size(mut self, min_size: u64) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn modified_after(mut self, modified_after: SystemTime) -> Self {
+        self.modified_after = Some(modified_after);
+        self
+    }
+
+    pub fn modified_before(mut self, modified_before: SystemTime) -> Self {
+        self.modified_before = Some(modified_before);
+        self
+    }
+
+    // entries directly inside the root have a depth of 1
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    fn is_match(&self, relative: &[String], entry: &suppaftp::list::File) -> bool {
+        let entry_type = match entry.is_directory() {
+            true => EntryType::Directory,
+            false => EntryType::File,
+        };
+        let size = entry.size() as u64;
+        let modified = entry.modified();
+        self.entry_type.is_none_or(|x| x == entry_type)
+            && (self.min_size.is_none() || entry_type == EntryType::File)
+            && (self.max_size.is_none() || entry_type == EntryType::File)
+            && self.min_size.is_none_or(|x| size >= x)
+            && self.max_size.is_none_or(|x| size <= x)
+            && self.modified_after.is_none_or(|x| modified >= x)
+            && self.modified_before.is_none_or(|x| modified <= x)
+            && self.pattern.as_ref().is_none_or(|x| x.is_match(relative))
+    }
+
+    fn should_descend(&self, relative: &[String]) -> bool {
+        self.max_depth.is_none_or(|x| relative.len() < x)
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|x| x.could_match_below(relative))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FindMatch {
+    pub path: FTPPath,
+    // components below the search root
+    pub relative: Vec<String>,
+    pub entry: suppaftp::list::File,
+}
+
+// walks the remote tree depth first, listing a directory only when the
+// iterator needs more entries. the connection is closed once the walk is
+// complete or the iterator is dropped.
+pub struct RemoteFind {
+    stream: Option<suppaftp::FtpStream>,
+    quit_stream: bool,
+    root: FTPPath,
+    query: FindQuery,
+    directories: Vec<Vec<String>>,
+    matches: VecDeque<FindMatch>,
+}
+
+impl RemoteFind {
+    pub(super) fn new(
+        stream: suppaftp::FtpStream,
+        quit_stream: bool,
+        root: &str,
+        query: FindQuery,
+    ) -> Self {
+        Self {
+            stream: Some(stream),
+            quit_stream,
+            root: FTPPath::from_str(root),
+            query,
+            directories: vec![Vec::new()],
+            matches: VecDeque::new(),
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(mut x) = self.stream.take() {
+            if self.quit_stream {
+                let _ = x.quit();
+            }
+        }
+    }
+
+    fn list(&mut self, relative: Vec<String>) -> GenericResult<()> {
+        let stream = match self.stream.as_mut() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let mut path = self.root.clone();
+        for part in &relative {
+            path.push(part);
+        }
+        debug!("Remote Find: Listing '{}'", path.to_string());
+        let mut directories = Vec::new();
+        for entry in super::list_directory_contents(stream, &path.to_string())? {
+            let mut entry_relative = relative.clone();
+            entry_relative.push(entry.name().to_string());
+            if entry.is_directory() && self.query.should_descend(&entry_relative) {
+                directories.push(entry_relative.clone());
+            }
+            if self.query.is_match(&entry_relative, &entry) {
+                let mut entry_path = path.clone();
+                entry_path.push(entry.name());
+                self.matches.push_back(FindMatch {
+                    path: entry_path,
+                    relative: entry_relative,
+                    entry,
+                });
+            }
+        }
+        // reversed so directories are visited in listing order
+        self.directories.extend(directories.into_iter().rev());
+        Ok(())
+    }
+}
+
+impl Iterator for RemoteFind {
+    type Item = GenericResult<FindMatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.matches.pop_front() {
+                return Some(Ok(x));
+            }
+            let relative = match self.directories.pop() {
+                Some(x) => x,
+                None => {
+                    self.close();
+                    return None;
+                }
+            };
+            if let Err(err) = self.list(relative) {
+                // the walk cannot continue on a connection in an unknown state
+                self.directories.clear();
+                self.close();
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+impl Drop for RemoteFind {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(value: &str) -> Vec<String> {
+        value.split('/').map(String::from).collect()
+    }
+
+    #[test]
+    fn match_any_depth() {
+        let pattern = GlobPattern::new("**/default.xex");
+        assert!(pattern.is_match(&path("default.xex")));
+        assert!(pattern.is_match(&path("Games/Halo 3/default.xex")));
+        assert!(!pattern.is_match(&path("Games/Halo 3/default.xex.bak")));
+        assert!(!pattern.is_match(&path("Games/Halo 3")));
+        assert!(pattern.could_match_below(&path("Games/Halo 3")));
+    }
+
+    #[test]
+    fn match_content_type() {
+        let pattern = GlobPattern::new("Content/*/*/000B0000/*");
+        assert!(pattern.is_match(&path("Content/0000000000000000/4D5307E6/000B0000/TU_12345")));
+        assert!(!pattern.is_match(&path("Content/0000000000000000/4D5307E6/00007000/TU_12345")));
+        assert!(!pattern.is_match(&path("Content/0000000000000000/4D5307E6/000B0000")));
+        assert!(!pattern.is_match(&path(
+            "Content/0000000000000000/4D5307E6/000B0000/TU_12345/extra"
+        )));
+    }
+
+    #[test]
+    fn prune_subtrees() {
+        let pattern = GlobPattern::new("Content/*/*/000B0000/*");
+        assert!(pattern.could_match_below(&path("Content")));
+        assert!(pattern.could_match_below(&path("Content/0000000000000000/4D5307E6")));
+        assert!(pattern.could_match_below(&path("Content/0000000000000000/4D5307E6/000B0000")));
+        assert!(!pattern.could_match_below(&path("Cache")));
+        assert!(!pattern.could_match_below(&path("Content/0000000000000000/4D5307E6/00007000")));
+        // nothing below a full match can match
+        assert!(!pattern
+            .could_match_below(&path("Content/0000000000000000/4D5307E6/000B0000/TU_12345")));
+    }
+
+    #[test]
+    fn match_single_character() {
+        let pattern = GlobPattern::new("default.xe?");
+        assert!(pattern.is_match(&path("default.xex")));
+        assert!(pattern.is_match(&path("default.xeb")));
+        assert!(!pattern.is_match(&path("default.xe")));
+        assert!(!pattern.is_match(&path("default.xexx")));
+        assert!(pattern.match_component("?a*", "ba"));
+        assert!(!pattern.match_component("?", ""));
+    }
+
+    #[test]
+    fn match_stars_with_backtracking() {
+        let pattern = GlobPattern::new("*");
+        assert!(pattern.match_component("a*b*c", "axxbyyc"));
+        assert!(pattern.match_component("a*b*c", "abcbc"));
+        assert!(!pattern.match_component("a*b*c", "axxbyy"));
+        assert!(pattern.match_component("*", ""));
+        assert!(pattern.match_component("**", "anything"));
+    }
+
+    #[test]
+    fn match_case_insensitive_by_default() {
+        let pattern = GlobPattern::new("**/DEFAULT.XEX");
+        assert!(pattern.is_match(&path("games/halo 3/default.xex")));
+        assert!(GlobPattern::new("content/*").could_match_below(&path("Content")));
+        let pattern = pattern.case_sensitive(true);
+        assert!(!pattern.is_match(&path("games/halo 3/default.xex")));
+        assert!(pattern.is_match(&path("Games/Halo 3/DEFAULT.XEX")));
+    }
+}
@@ -0,0 +1,320 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::transfer::{self, CancellationToken, TransferItem, TransferOptions, TransferPlan};
+use super::{FTPPath, FtpClient};
+use crate::utils::GenericResult;
+use log::{debug, warn};
+
+pub const DEFAULT_POOL_CONNECTIONS: usize = 4;
+pub const DEFAULT_POOL_RETRIES: usize = 2;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PoolDirection {
+    Download,
+    Upload,
+}
+
+#[derive(Debug)]
+pub struct PoolFileResult {
+    pub direction: PoolDirection,
+    pub remote: String,
+    pub local: PathBuf,
+    pub attempts: usize,
+    pub result: GenericResult<()>,
+}
+
+#[derive(Debug, Default)]
+pub struct PoolReport {
+    pub files: Vec<PoolFileResult>,
+}
+
+impl PoolReport {
+    pub fn succeeded(&self) -> impl Iterator<Item = &PoolFileResult> {
+        self.files.iter().filter(|x| x.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &PoolFileResult> {
+        self.files.iter().filter(|x| x.result.is_err())
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.files.iter().all(|x| x.result.is_ok())
+    }
+}
+
+struct Job {
+    direction: PoolDirection,
+    remote: String,
+    local: PathBuf,
+    // unknown sizes are looked up by the worker that picks up the job
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    attempts: usize,
+}
+
+// transfers files over up to `connections` FTP connections at once. a file
+// that fails is retried on a fresh connection up to `retries` more times.
+#[derive(Clone, Debug)]
+pub struct TransferPool {
+    client: FtpClient,
+    connections: usize,
+    retries: usize,
+    options: TransferOptions,
+}
+
+impl TransferPool {
+    pub fn new(client: FtpClient) -> Self {
+        Self {
+            client,
+            connections: DEFAULT_POOL_CONNECTIONS,
+            retries: DEFAULT_POOL_RETRIES,
+            options: TransferOptions::new(),
+        }
+    }
+
+    pub fn connections(mut self, connections: usize) -> Self {
+        self.connections = connections.max(1);
+        self
+    }
+
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    // files that have not been started when the token is cancelled are
+    // reported as cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.options = self.options.cancellation_token(token);
+        self
+    }
+
+    pub fn resume(mut self, resume: bool) -> Self {
+        self.options = self.options.resume(resume);
+        self
+    }
+
+    pub fn download_files(&self, files: &[(String, PathBuf)]) -> PoolReport {
+        let jobs = files
+            .iter()
+            .map(|(remote, local)| Job {
+                direction: PoolDirection::Download,
+                remote: remote.clone(),
+                local: local.clone(),
+                size: None,
+                modified: None,
+                attempts: 0,
+            })
+            .collect();
+        self.run(jobs, false)
+    }
+
+    pub fn upload_files(&self, files: &[(PathBuf, String)]) -> GenericResult<PoolReport> {
+        // FATX ignores case, so parents that only differ in case are the same
+        let mut seen = BTreeSet::new();
+        let parents = files
+            .iter()
+            .filter_map(|(_, remote)| FTPPath::from_str(remote).parent_string())
+            .filter(|x| seen.insert(x.to_lowercase()))
+            .collect();
+        self.create_remote_directories(parents)?;
+        let jobs = files
+            .iter()
+            .map(|(local, remote)| Job {
+                direction: PoolDirection::Upload,
+                remote: remote.clone(),
+                local: local.clone(),
+                size: None,
+                modified: None,
+                attempts: 0,
+            })
+            .collect();
+        Ok(self.run(jobs, true))
+    }
+
+    pub fn download_directory(
+        &self,
+        remote_dir: &str,
+        local_dir: &Path,
+    ) -> GenericResult<PoolReport> {
+        let mut plan = TransferPlan::default();
+        let mut stream = self.client.new_ftp_stream()?;
+        let result =
+            transfer::plan_download_directory(&mut stream, remote_dir, local_dir, &mut plan);
+        let _ = stream.quit();
+        result?;
+        for directory in &plan.local_directories {
+            std::fs::create_dir_all(directory)?;
+        }
+        Ok(self.run(Self::jobs(PoolDirection::Download, plan.files), true))
+    }
+
+    pub fn upload_directory(
+        &self,
+        local_dir: &Path,
+        remote_dir: &str,
+    ) -> GenericResult<PoolReport> {
+        let mut plan = TransferPlan::default();
+        transfer::plan_upload_directory(local_dir, remote_dir, &mut plan)?;
+        self.create_remote_directories(std::mem::take(&mut plan.remote_directories))?;
+        Ok(self.run(Self::jobs(PoolDirection::Upload, plan.files), true))
+    }
+
+    // directories are created up front over a single connection so the
+    // workers do not race to create them
+    fn create_remote_directories(&self, remote_directories: Vec<String>) -> GenericResult<()> {
+        if remote_directories.is_empty() {
+            return Ok(());
+        }
+        let directories = TransferPlan {
+            remote_directories,
+            ..Default::default()
+        };
+        let mut stream = self.client.new_ftp_stream()?;
+        let result = transfer::upload(&mut stream, &directories, &TransferOptions::new());
+        let _ = stream.quit();
+        result
+    }
+
+    fn jobs(direction: PoolDirection, files: Vec<TransferItem>) -> VecDeque<Job> {
+        files
+            .into_iter()
+            .map(|x| Job {
+                direction,
+                remote: x.remote,
+                local: x.local,
+                size: Some(x.size),
+                modified: x.modified,
+                attempts: 0,
+            })
+            .collect()
+    }
+
+    fn run(&self, jobs: VecDeque<Job>, parents_exist: bool) -> PoolReport {
+        let workers = self.connections.min(jobs.len());
+        let queue = Mutex::new(jobs);
+        let results = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for worker in 0..workers {
+                let queue = &queue;
+                let results = &results;
+                scope.spawn(move || self.work(worker, queue, results, parents_exist));
+            }
+        });
+        let mut files = results.into_inner().unwrap_or_else(|x| x.into_inner());
+        files.sort_by(|a: &PoolFileResult, b| a.remote.cmp(&b.remote));
+        PoolReport { files }
+    }
+
+    fn work(
+        &self,
+        worker: usize,
+        queue: &Mutex<VecDeque<Job>>,
+        results: &Mutex<Vec<PoolFileResult>>,
+        parents_exist: bool,
+    ) {
+        let mut stream: Option<suppaftp::FtpStream> = None;
+        loop {
+            let job = match queue.lock() {
+                Ok(mut x) => x.pop_front(),
+                Err(_) => None,
+            };
+            let mut job = match job {
+                Some(x) => x,
+                None => break,
+            };
+            job.attempts += 1;
+            let result = match self.options.is_cancelled() {
+                true => Err(transfer::TransferCancelled.into()),
+                false => self.transfer(&mut stream, &job, parents_exist),
+            };
+            if let Err(err) = &result {
+                // the connection may be in an unknown state after a failure
+                if let Some(mut x) = stream.take() {
+                    let _ = x.quit();
+                }
+                if job.attempts <= self.retries && !transfer::is_cancelled_error(err) {
+                    warn!(
+                        "Transfer Pool: Worker {} failed to transfer '{}' (attempt {}), retrying. Got the following error: {}",
+                        worker, job.remote, job.attempts, err
+                    );
+                    if let Ok(mut x) = queue.lock() {
+                        x.push_back(job);
+                    }
+                    continue;
+                }
+            }
+            debug!(
+                "Transfer Pool: Worker {} finished '{}' after {} attempt(s)",
+                worker, job.remote, job.attempts
+            );
+            if let Ok(mut x) = results.lock() {
+                x.push(PoolFileResult {
+                    direction: job.direction,
+                    remote: job.remote,
+                    local: job.local,
+                    attempts: job.attempts,
+                    result,
+                });
+            }
+        }
+        if let Some(mut x) = stream {
+            let _ = x.quit();
+        }
+    }
+
+    fn transfer(
+        &self,
+        stream: &mut Option<suppaftp::FtpStream>,
+        job: &Job,
+        parents_exist: bool,
+    ) -> GenericResult<()> {
+        let ftp_stream = match stream {
+            Some(x) => x,
+            None => stream.insert(self.client.new_ftp_stream()?),
+        };
+        let mut plan = match (job.direction, job.size) {
+            (_, Some(size)) => TransferPlan {
+                files: vec![TransferItem {
+                    remote: job.remote.clone(),
+                    local: job.local.clone(),
+                    size,
+                    modified: job.modified,
+                }],
+                ..Default::default()
+            },
+            (PoolDirection::Download, None) => {
+                transfer::plan_download_file(ftp_stream, &job.remote, &job.local)?
+            }
+            (PoolDirection::Upload, None) => transfer::plan_upload_file(&job.local, &job.remote)?,
+        };
+        plan.skip_parent_creation = parents_exist;
+        match job.direction {
+            PoolDirection::Download => transfer::download(ftp_stream, &plan, &self.options),
+            PoolDirection::Upload => transfer::upload(ftp_stream, &plan, &self.options),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+use std::io::{Read, Write};
+
+use super::FTPPath;
+use crate::utils::{GenericError, GenericResult};
+use log::{debug, error, warn};
+
+// reads a remote file as it is retrieved. `finish` ends the transfer and
+// reports errors from the server, dropping the reader ends it silently.
+pub struct RemoteReader {
+    stream: Option<suppaftp::FtpStream>,
+    data_stream: Option<Box<dyn Read + Send>>,
+    quit_stream: bool,
+    remote: String,
+}
+
+impl RemoteReader {
+    pub(super) fn open(
+        mut stream: suppaftp::FtpStream,
+        quit_stream: bool,
+        remote_file: &str,
+    ) -> GenericResult<Self> {
+        let remote = FTPPath::from_str(remote_file).to_string();
+        debug!("Remote Reader: calling retr_as_stream('{}')", remote);
+        let data_stream = match stream.retr_as_stream(&remote) {
+            Ok(x) => x,
+            Err(err) => {
+                if quit_stream {
+                    let _ = stream.quit();
+                }
+                return Err(err.into());
+            }
+        };
+        Ok(Self {
+            stream: Some(stream),
+            data_stream: Some(Box::new(data_stream)),
+            quit_stream,
+            remote,
+        })
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    pub fn finish(mut self) -> GenericResult<()> {
+        self.close()
+    }
+
+    fn close(&mut self) -> GenericResult<()> {
+        let mut stream = match self.stream.take() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let result = match self.data_stream.take() {
+            Some(x) => stream.finalize_retr_stream(x),
+            None => Ok(()),
+        };
+        if self.quit_stream {
+            match result {
+                Ok(_) => stream.quit()?,
+                Err(_) => {
+                    let _ = stream.quit();
+                }
+            }
+        }
+        Ok(result?)
+    }
+}
+
+impl Read for RemoteReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.data_stream.as_mut() {
+            Some(x) => x.read(buf),
+            None => Ok(0),
+        }
+    }
+}
+
+impl Drop for RemoteReader {
+    fn drop(&mut self) {
+        // the server usually answers an unfinished retrieve with an error
+        let _ = self.close();
+    }
+}
+
+// the data connection of an upload, it is also read so it can be aborted
+trait UploadStream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> UploadStream for T {}
+
+// writes a remote file as it is stored. only `finish` commits the upload,
+// dropping the writer without it aborts the transfer and deletes the partial
+// remote file.
+pub struct RemoteWriter {
+    stream: Option<suppaftp::FtpStream>,
+    data_stream: Option<Box<dyn UploadStream>>,
+    quit_stream: bool,
+    remote: String,
+}
+
+impl RemoteWriter {
+    pub(super) fn open(
+        mut stream: suppaftp::FtpStream,
+        quit_stream: bool,
+        remote_file: &str,
+    ) -> GenericResult<Self> {
+        let remote_path = FTPPath::from_str(remote_file);
+        let remote = remote_path.to_string();
+        let result = match remote_path.parent_string() {
+            Some(x) => super::create_directory(&mut stream, &x),
+            None => Ok(()),
+        };
+        debug!("Remote Writer: calling put_with_stream('{}')", remote);
+        let data_stream = match result.and_then(|_| Ok(stream.put_with_stream(&remote)?)) {
+            Ok(x) => x,
+            Err(err) => {
+                if quit_stream {
+                    let _ = stream.quit();
+                }
+                return Err(err);
+            }
+        };
+        Ok(Self {
+            stream: Some(stream),
+            data_stream: Some(Box::new(data_stream)),
+            quit_stream,
+            remote,
+        })
+    }
+
+    pub fn remote(&self) -> &str {
+        &self.remote
+    }
+
+    pub fn finish(mut self) -> GenericResult<()> {
+        let mut stream = match self.stream.take() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let result = match self.data_stream.take() {
+            Some(mut x) => match x.flush() {
+                Ok(_) => stream.finalize_put_stream(x).map_err(|x| x.into()),
+                Err(err) => Err(err.into()),
+            },
+            None => Ok(()),
+        };
+        self.end(stream, result, "finish writing")
+    }
+
+    // ABOR closes the data connection, the server may still have stored
+    // what was written so far so the remote file is deleted afterwards
+    fn abort(&mut self) -> GenericResult<()> {
+        let mut stream = match self.stream.take() {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        debug!("Remote Writer: Aborting upload of '{}'", self.remote);
+        let result = match self.data_stream.take() {
+            Some(x) => stream
+                .abort(x)
+                .map_err(GenericError::from)
+                .and_then(|_| super::delete_file(&mut stream, &self.remote)),
+            None => Ok(()),
+        };
+        self.end(stream, result, "abort writing")
+    }
+
+    fn end(
+        &self,
+        mut stream: suppaftp::FtpStream,
+        result: GenericResult<()>,
+        action: &str,
+    ) -> GenericResult<()> {
+        if self.quit_stream {
+            match result {
+                Ok(_) => stream.quit()?,
+                Err(_) => {
+                    let _ = stream.quit();
+                }
+            }
+        }
+        if let Err(err) = &result {
+            error!(
+                "Remote Writer: Failed to {} '{}'. Got the following error: {}",
+                action, self.remote, err
+            );
+        }
+        result
+    }
+}
+
+impl Write for RemoteWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self.data_stream.as_mut() {
+            Some(x) => x.write(buf),
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "remote writer is already finished",
+            )),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self.data_stream.as_mut() {
+            Some(x) => x.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RemoteWriter {
+    fn drop(&mut self) {
+        if self.stream.is_some() {
+            warn!(
+                "Remote Writer: '{}' was dropped without calling finish, discarding it",
+                self.remote
+            );
+            let _ = self.abort();
+        }
+    }
+}
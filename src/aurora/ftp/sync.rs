@@ -0,0 +1,777 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::transfer::{self, TransferItem, TransferOptions, TransferPlan};
+use super::FTPPath;
+use crate::utils::GenericResult;
+use log::{debug, error, warn};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SyncDirection {
+    Download,
+    Upload,
+    Bidirectional,
+}
+
+#[derive(Clone, Debug)]
+pub struct SyncOptions {
+    direction: SyncDirection,
+    delete_extraneous: bool,
+    pub(super) dry_run: bool,
+    mtime_tolerance: Duration,
+    remote_utc_offset: i64,
+    pub(super) transfer_options: TransferOptions,
+}
+
+impl SyncOptions {
+    pub fn new(direction: SyncDirection) -> Self {
+        Self {
+            direction,
+            delete_extraneous: false,
+            dry_run: false,
+            mtime_tolerance: Duration::from_secs(60),
+            remote_utc_offset: 0,
+            transfer_options: TransferOptions::new(),
+        }
+    }
+
+    // removes files and directories from the destination that do not exist
+    // in the source. only applies to one-way syncs, a bidirectional sync
+    // never deletes anything.
+    pub fn delete_extraneous(mut self, delete_extraneous: bool) -> Self {
+        self.delete_extraneous = delete_extraneous;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    // FTP listings only report modification times to the minute (or the day
+    // for older files), so smaller differences are ignored
+    pub fn mtime_tolerance(mut self, mtime_tolerance: Duration) -> Self {
+        self.mtime_tolerance = mtime_tolerance;
+        self
+    }
+
+    // the console lists modification times in its own time zone without
+    // saying which one. set this to the console's offset from UTC in seconds,
+    // e.g. -18000 for UTC-5, so remote and local times can be compared.
+    pub fn remote_utc_offset(mut self, seconds: i64) -> Self {
+        self.remote_utc_offset = seconds;
+        self
+    }
+
+    pub fn transfer_options(mut self, transfer_options: TransferOptions) -> Self {
+        self.transfer_options = transfer_options;
+        self
+    }
+
+    // converts a listed remote time, which is read as UTC, to actual UTC
+    fn remote_time(&self, listed: SystemTime) -> SystemTime {
+        let offset = Duration::from_secs(self.remote_utc_offset.unsigned_abs());
+        match self.remote_utc_offset >= 0 {
+            true => listed.checked_sub(offset).unwrap_or(listed),
+            false => listed.checked_add(offset).unwrap_or(listed),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SyncReason {
+    Missing,
+    SizeChanged,
+    Newer,
+}
+
+impl fmt::Display for SyncReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            SyncReason::Missing => "missing",
+            SyncReason::SizeChanged => "size changed",
+            SyncReason::Newer => "newer",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SyncAction {
+    CreateLocalDirectory(PathBuf),
+    CreateRemoteDirectory(String),
+    Download {
+        remote: String,
+        local: PathBuf,
+        size: u64,
+        modified: SystemTime,
+        reason: SyncReason,
+    },
+    Upload {
+        local: PathBuf,
+        remote: String,
+        size: u64,
+        reason: SyncReason,
+    },
+    DeleteLocal(PathBuf),
+    DeleteRemote {
+        remote: String,
+        is_directory: bool,
+    },
+    // both sides changed and it cannot be decided which one to keep
+    Conflict {
+        local: PathBuf,
+        remote: String,
+    },
+}
+
+impl fmt::Display for SyncAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SyncAction::CreateLocalDirectory(x) => write!(f, "mkdir local  {}", x.display()),
+            SyncAction::CreateRemoteDirectory(x) => write!(f, "mkdir remote {}", x),
+            SyncAction::Download {
+                remote,
+                local,
+                size,
+                reason,
+                ..
+            } => write!(
+                f,
+                "download     {} -> {} ({} bytes, {})",
+                remote,
+                local.display(),
+                size,
+                reason
+            ),
+            SyncAction::Upload {
+                local,
+                remote,
+                size,
+                reason,
+            } => write!(
+                f,
+                "upload       {} -> {} ({} bytes, {})",
+                local.display(),
+                remote,
+                size,
+                reason
+            ),
+            SyncAction::DeleteLocal(x) => write!(f, "delete local  {}", x.display()),
+            SyncAction::DeleteRemote { remote, .. } => write!(f, "delete remote {}", remote),
+            SyncAction::Conflict { local, remote } => {
+                write!(f, "conflict     {} <-> {}", local.display(), remote)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SyncPlan {
+    pub direction: SyncDirection,
+    pub actions: Vec<SyncAction>,
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for action in &self.actions {
+            writeln!(f, "{}", action)?;
+        }
+        Ok(())
+    }
+}
+
+impl SyncPlan {
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &SyncAction> {
+        self.actions
+            .iter()
+            .filter(|x| matches!(x, SyncAction::Conflict { .. }))
+    }
+
+    pub fn bytes_to_transfer(&self) -> u64 {
+        self.actions
+            .iter()
+            .map(|x| match x {
+                SyncAction::Download { size, .. } | SyncAction::Upload { size, .. } => *size,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    // the path as it is spelled on this side
+    relative: Vec<String>,
+    is_directory: bool,
+    size: u64,
+    modified: SystemTime,
+}
+
+// entries keyed by their lowercased path relative to the synced directory,
+// since FATX ignores case. parents are ordered before their children.
+type Tree = BTreeMap<Vec<String>, Entry>;
+
+fn insert(tree: &mut Tree, entry: Entry) -> bool {
+    let key: Vec<String> = entry.relative.iter().map(|x| x.to_lowercase()).collect();
+    if let Some(x) = tree.get(&key) {
+        warn!(
+            "Sync: Skipping '{}' because it only differs in case from '{}'",
+            entry.relative.join("/"),
+            x.relative.join("/")
+        );
+        return false;
+    }
+    tree.insert(key, entry);
+    true
+}
+
+// spells `key` the way `tree` does for as much of it as exists there and the
+// way `entry` does below that
+fn spelled(tree: &Tree, key: &[String], entry: &Entry) -> Vec<String> {
+    for len in (1..=key.len()).rev() {
+        if let Some(x) = tree.get(&key[..len]) {
+            let mut relative = x.relative.clone();
+            relative.extend_from_slice(&entry.relative[len..]);
+            return relative;
+        }
+    }
+    entry.relative.clone()
+}
+
+fn remote_tree(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_dir: &FTPPath,
+    relative: &mut Vec<String>,
+    tree: &mut Tree,
+    options: &SyncOptions,
+) -> GenericResult<()> {
+    let mut remote_path = remote_dir.clone();
+    for part in relative.iter() {
+        remote_path.push(part);
+    }
+    for entry in super::list_directory_contents(ftp_stream, &remote_path.to_string())? {
+        if entry.is_symlink() {
+            continue;
+        }
+        relative.push(entry.name().to_string());
+        let inserted = insert(
+            tree,
+            Entry {
+                relative: relative.clone(),
+                is_directory: entry.is_directory(),
+                size: entry.size() as u64,
+                modified: options.remote_time(entry.modified()),
+            },
+        );
+        if inserted && entry.is_directory() {
+            remote_tree(ftp_stream, remote_dir, relative, tree, options)?;
+        }
+        relative.pop();
+    }
+    Ok(())
+}
+
+fn local_tree(local_dir: &Path, relative: &mut Vec<String>, tree: &mut Tree) -> GenericResult<()> {
+    let mut local_path = local_dir.to_path_buf();
+    for part in relative.iter() {
+        local_path.push(part);
+    }
+    for entry in std::fs::read_dir(&local_path)? {
+        let entry = entry?;
+        let name = match entry.file_name().to_str() {
+            Some(x) => x.to_string(),
+            None => {
+                warn!(
+                    "Sync: Skipping '{}' because its name is not valid UTF-8",
+                    entry.path().display()
+                );
+                continue;
+            }
+        };
+        let metadata = entry.metadata()?;
+        relative.push(name);
+        let inserted = insert(
+            tree,
+            Entry {
+                relative: relative.clone(),
+                is_directory: metadata.is_dir(),
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            },
+        );
+        if inserted && metadata.is_dir() {
+            local_tree(local_dir, relative, tree)?;
+        }
+        relative.pop();
+    }
+    Ok(())
+}
+
+fn is_newer(a: SystemTime, b: SystemTime, tolerance: Duration) -> bool {
+    a.duration_since(b).is_ok_and(|x| x > tolerance)
+}
+
+// returns true for paths that are below a path that is already handled
+fn below_any(relative: &[String], handled: &[Vec<String>]) -> bool {
+    handled
+        .iter()
+        .any(|x| relative.len() > x.len() && relative.starts_with(x))
+}
+
+pub(super) fn plan(
+    ftp_stream: &mut suppaftp::FtpStream,
+    local_dir: &Path,
+    remote_dir: &str,
+    options: &SyncOptions,
+) -> GenericResult<SyncPlan> {
+    let remote_root = FTPPath::from_str(remote_dir);
+    let remote_exists = match super::list_entry(ftp_stream, &remote_root.to_string())? {
+        Some(x) if !x.is_directory() => {
+            return Err(format!("Remote path '{}' is not a directory.", remote_dir).into());
+        }
+        Some(_) => true,
+        // the root of a drive cannot be listed as an entry
+        None => remote_root.parts.len() <= 1,
+    };
+    if local_dir.is_file() {
+        return Err(format!("Local path '{}' is not a directory.", local_dir.display()).into());
+    }
+    let local_exists = local_dir.is_dir();
+    check_roots(
+        options.direction,
+        (remote_dir, remote_exists),
+        (local_dir, local_exists),
+    )?;
+    let mut remote = Tree::new();
+    if remote_exists {
+        remote_tree(
+            ftp_stream,
+            &remote_root,
+            &mut Vec::new(),
+            &mut remote,
+            options,
+        )?;
+    }
+    let mut local = Tree::new();
+    if local_exists {
+        local_tree(local_dir, &mut Vec::new(), &mut local)?;
+    }
+    let plan = compare(
+        (&remote_root, remote_exists, &remote),
+        (local_dir, local_exists, &local),
+        options,
+    );
+    debug!("Sync: Planned {} action(s)", plan.actions.len());
+    Ok(plan)
+}
+
+// only the destination may be missing, a missing source is more likely a
+// wrong path than an empty directory
+fn check_roots(
+    direction: SyncDirection,
+    (remote_dir, remote_exists): (&str, bool),
+    (local_dir, local_exists): (&Path, bool),
+) -> GenericResult<()> {
+    match direction {
+        SyncDirection::Download if !remote_exists => {
+            error!("Sync: Remote directory '{}' does not exist.", remote_dir);
+            return Err(format!("Remote directory '{}' does not exist.", remote_dir).into());
+        }
+        SyncDirection::Upload if !local_exists => {
+            error!(
+                "Sync: Local directory '{}' does not exist.",
+                local_dir.display()
+            );
+            return Err(
+                format!("Local directory '{}' does not exist.", local_dir.display()).into(),
+            );
+        }
+        SyncDirection::Bidirectional if !remote_exists && !local_exists => {
+            error!(
+                "Sync: Neither '{}' nor '{}' exist.",
+                local_dir.display(),
+                remote_dir
+            );
+            return Err(format!(
+                "Neither '{}' nor '{}' exist.",
+                local_dir.display(),
+                remote_dir
+            )
+            .into());
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+fn compare(
+    (remote_root, remote_exists, remote): (&FTPPath, bool, &Tree),
+    (local_dir, local_exists, local): (&Path, bool, &Tree),
+    options: &SyncOptions,
+) -> SyncPlan {
+    let remote_path = |relative: &[String]| {
+        let mut x = remote_root.clone();
+        for part in relative {
+            x.push(part);
+        }
+        x.to_string()
+    };
+    let local_path = |relative: &[String]| {
+        let mut x = local_dir.to_path_buf();
+        for part in relative {
+            x.push(part);
+        }
+        x
+    };
+    let download = options.direction != SyncDirection::Upload;
+    let upload = options.direction != SyncDirection::Download;
+    let mut plan = SyncPlan {
+        direction: options.direction,
+        actions: Vec::new(),
+    };
+    if download && !local_exists {
+        plan.actions
+            .push(SyncAction::CreateLocalDirectory(local_dir.to_path_buf()));
+    }
+    if upload && !remote_exists {
+        plan.actions
+            .push(SyncAction::CreateRemoteDirectory(remote_root.to_string()));
+    }
+    // source entries whose type differs from the destination are reported as
+    // conflicts and everything below them is skipped
+    let mut skipped: Vec<Vec<String>> = Vec::new();
+    let mut deleted: Vec<Vec<String>> = Vec::new();
+    let mut keys: Vec<&Vec<String>> = remote.keys().chain(local.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        if below_any(key, &skipped) || below_any(key, &deleted) {
+            continue;
+        }
+        let (r, l) = (remote.get(key), local.get(key));
+        let entry = match r.or(l) {
+            Some(x) => x,
+            None => continue,
+        };
+        // both sides are addressed the way they already spell the path
+        let remote_file = remote_path(&spelled(remote, key, entry));
+        let local_file = local_path(&spelled(local, key, entry));
+        match (r, l) {
+            (Some(r), None) if download => match r.is_directory {
+                true => plan
+                    .actions
+                    .push(SyncAction::CreateLocalDirectory(local_file)),
+                false => plan.actions.push(SyncAction::Download {
+                    remote: remote_file,
+                    local: local_file,
+                    size: r.size,
+                    modified: r.modified,
+                    reason: SyncReason::Missing,
+                }),
+            },
+            (Some(r), None) => {
+                if options.delete_extraneous {
+                    plan.actions.push(SyncAction::DeleteRemote {
+                        remote: remote_file,
+                        is_directory: r.is_directory,
+                    });
+                    deleted.push(key.clone());
+                }
+            }
+            (None, Some(l)) if upload => match l.is_directory {
+                true => plan
+                    .actions
+                    .push(SyncAction::CreateRemoteDirectory(remote_file)),
+                false => plan.actions.push(SyncAction::Upload {
+                    local: local_file,
+                    remote: remote_file,
+                    size: l.size,
+                    reason: SyncReason::Missing,
+                }),
+            },
+            (None, Some(_)) => {
+                if options.delete_extraneous {
+                    plan.actions.push(SyncAction::DeleteLocal(local_file));
+                    deleted.push(key.clone());
+                }
+            }
+            (Some(r), Some(l)) => {
+                let conflict = SyncAction::Conflict {
+                    local: local_file.clone(),
+                    remote: remote_file.clone(),
+                };
+                if r.is_directory != l.is_directory {
+                    plan.actions.push(conflict);
+                    skipped.push(key.clone());
+                    continue;
+                }
+                if r.is_directory {
+                    continue;
+                }
+                let remote_newer = is_newer(r.modified, l.modified, options.mtime_tolerance);
+                let local_newer = is_newer(l.modified, r.modified, options.mtime_tolerance);
+                let size_changed = r.size != l.size;
+                let reason = match size_changed {
+                    true => SyncReason::SizeChanged,
+                    false => SyncReason::Newer,
+                };
+                let download_action = SyncAction::Download {
+                    remote: remote_file.clone(),
+                    local: local_file.clone(),
+                    size: r.size,
+                    modified: r.modified,
+                    reason,
+                };
+                let upload_action = SyncAction::Upload {
+                    local: local_file,
+                    remote: remote_file,
+                    size: l.size,
+                    reason,
+                };
+                match options.direction {
+                    SyncDirection::Download if size_changed || remote_newer => {
+                        plan.actions.push(download_action)
+                    }
+                    SyncDirection::Upload if size_changed || local_newer => {
+                        plan.actions.push(upload_action)
+                    }
+                    SyncDirection::Bidirectional if remote_newer => {
+                        plan.actions.push(download_action)
+                    }
+                    SyncDirection::Bidirectional if local_newer => plan.actions.push(upload_action),
+                    SyncDirection::Bidirectional if size_changed => {
+                        plan.actions.push(conflict);
+                        skipped.push(key.clone());
+                    }
+                    _ => (),
+                }
+            }
+            (None, None) => (),
+        }
+    }
+    plan
+}
+
+// directories are created first, then files are transferred and finally
+// extraneous entries are deleted. conflicts are left untouched.
+pub(super) fn execute(
+    ftp_stream: &mut suppaftp::FtpStream,
+    plan: &SyncPlan,
+    options: &SyncOptions,
+) -> GenericResult<()> {
+    let mut downloads = TransferPlan::default();
+    let mut uploads = TransferPlan::default();
+    for action in &plan.actions {
+        match action {
+            SyncAction::CreateLocalDirectory(x) => downloads.local_directories.push(x.clone()),
+            SyncAction::CreateRemoteDirectory(x) => uploads.remote_directories.push(x.clone()),
+            SyncAction::Download {
+                remote,
+                local,
+                size,
+                modified,
+                ..
+            } => downloads.files.push(TransferItem {
+                remote: remote.clone(),
+                local: local.clone(),
+                size: *size,
+                modified: Some(*modified),
+            }),
+            SyncAction::Upload {
+                local,
+                remote,
+                size,
+                ..
+            } => uploads.files.push(TransferItem {
+                remote: remote.clone(),
+                local: local.clone(),
+                size: *size,
+                modified: None,
+            }),
+            _ => (),
+        }
+    }
+    if !downloads.local_directories.is_empty() || !downloads.files.is_empty() {
+        transfer::download(ftp_stream, &downloads, &options.transfer_options)?;
+    }
+    if !uploads.remote_directories.is_empty() || !uploads.files.is_empty() {
+        transfer::upload(ftp_stream, &uploads, &options.transfer_options)?;
+    }
+    // the console sets the modification time of uploaded files to the time
+    // of the upload. copy it to the local file so the next bidirectional
+    // sync does not download the file again.
+    if plan.direction == SyncDirection::Bidirectional {
+        for item in &uploads.files {
+            if let Some(x) = super::list_entry(ftp_stream, &item.remote)? {
+                std::fs::File::options()
+                    .write(true)
+                    .open(&item.local)?
+                    .set_modified(options.remote_time(x.modified()))?;
+            }
+        }
+    }
+    for action in &plan.actions {
+        match action {
+            SyncAction::DeleteLocal(x) => {
+                debug!("Sync: Deleting local '{}'", x.display());
+                match x.is_dir() {
+                    true => std::fs::remove_dir_all(x)?,
+                    false => std::fs::remove_file(x)?,
+                }
+            }
+            SyncAction::DeleteRemote {
+                remote,
+                is_directory,
+            } => {
+                debug!("Sync: Deleting remote '{}'", remote);
+                match is_directory {
+                    true => super::delete_directory(ftp_stream, remote)?,
+                    false => super::delete_file(ftp_stream, remote)?,
+                }
+            }
+            SyncAction::Conflict { local, remote } => {
+                warn!(
+                    "Sync: Skipping conflict between '{}' and '{}'",
+                    local.display(),
+                    remote
+                );
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    // (path, size or `None` for a directory, modification time in seconds)
+    fn tree(entries: &[(&str, Option<u64>, u64)]) -> Tree {
+        let mut tree = Tree::new();
+        for (path, size, modified) in entries {
+            insert(
+                &mut tree,
+                Entry {
+                    relative: path.split('/').map(|x| x.to_string()).collect(),
+                    is_directory: size.is_none(),
+                    size: size.unwrap_or(0),
+                    modified: UNIX_EPOCH + Duration::from_secs(*modified),
+                },
+            );
+        }
+        tree
+    }
+
+    fn download(remote: &Tree, local: &Tree) -> SyncPlan {
+        compare(
+            (&FTPPath::from_str("/Hdd1/Saves"), true, remote),
+            (Path::new("/saves"), true, local),
+            &SyncOptions::new(SyncDirection::Download),
+        )
+    }
+
+    #[test]
+    fn case_only_differences_match() {
+        let remote = tree(&[("Content", None, 0), ("Content/Save.DAT", Some(10), 600)]);
+        let local = tree(&[("content", None, 0), ("content/save.dat", Some(10), 600)]);
+        assert!(download(&remote, &local).is_empty());
+    }
+
+    #[test]
+    fn missing_files_keep_the_destination_spelling() {
+        let remote = tree(&[("SAVES", None, 0), ("SAVES/B.txt", Some(1), 0)]);
+        let local = tree(&[("Saves", None, 0)]);
+        let plan = download(&remote, &local);
+        assert_eq!(plan.actions.len(), 1);
+        match &plan.actions[0] {
+            SyncAction::Download { remote, local, .. } => {
+                assert_eq!(remote, "/Hdd1/Saves/SAVES/B.txt");
+                assert_eq!(local, Path::new("/saves/Saves/B.txt"));
+            }
+            x => panic!("unexpected action {}", x),
+        }
+    }
+
+    #[test]
+    fn case_duplicates_are_skipped() {
+        let local = tree(&[("a.txt", Some(1), 0), ("A.TXT", Some(2), 0)]);
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[&vec!["a.txt".to_string()]].size, 1);
+    }
+
+    #[test]
+    fn listing_precision_is_tolerated() {
+        let local = tree(&[("a", Some(1), 600)]);
+        assert!(download(&tree(&[("a", Some(1), 630)]), &local).is_empty());
+        let plan = download(&tree(&[("a", Some(1), 720)]), &local);
+        assert!(matches!(
+            plan.actions[..],
+            [SyncAction::Download {
+                reason: SyncReason::Newer,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn remote_times_are_converted_to_utc() {
+        let listed = UNIX_EPOCH + Duration::from_secs(7200);
+        let ahead = SyncOptions::new(SyncDirection::Download).remote_utc_offset(3600);
+        let behind = SyncOptions::new(SyncDirection::Download).remote_utc_offset(-3600);
+        assert_eq!(
+            ahead.remote_time(listed),
+            UNIX_EPOCH + Duration::from_secs(3600)
+        );
+        assert_eq!(
+            behind.remote_time(listed),
+            UNIX_EPOCH + Duration::from_secs(10800)
+        );
+    }
+
+    #[test]
+    fn missing_source_is_an_error() {
+        let local = Path::new("/saves");
+        let check = |direction, remote_exists, local_exists| {
+            check_roots(
+                direction,
+                ("/Hdd1/Saves", remote_exists),
+                (local, local_exists),
+            )
+            .is_ok()
+        };
+        assert!(!check(SyncDirection::Download, false, true));
+        assert!(check(SyncDirection::Download, true, false));
+        assert!(!check(SyncDirection::Upload, true, false));
+        assert!(check(SyncDirection::Upload, false, true));
+        assert!(check(SyncDirection::Bidirectional, false, true));
+        assert!(!check(SyncDirection::Bidirectional, false, false));
+    }
+}
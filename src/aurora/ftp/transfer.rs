@@ -0,0 +1,818 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::verify::{self, VerifyOptions};
+use super::FTPPath;
+use crate::utils::{create_parent_directories, GenericError, GenericResult};
+use log::{debug, error, warn};
+
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+// FAT file systems keep modification times with two second precision
+const RESUME_MTIME_TOLERANCE: Duration = Duration::from_secs(2);
+
+pub type ProgressCallback = Arc<dyn Fn(&TransferProgress) + Send + Sync>;
+
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug)]
+pub struct TransferCancelled;
+
+impl fmt::Display for TransferCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transfer was cancelled.")
+    }
+}
+
+impl std::error::Error for TransferCancelled {}
+
+pub fn is_cancelled_error(err: &GenericError) -> bool {
+    err.downcast_ref::<TransferCancelled>().is_some()
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TransferProgress {
+    pub current_file: String,
+    pub file_bytes_done: u64,
+    pub file_bytes_total: u64,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_per_second: f64,
+}
+
+#[derive(Clone)]
+pub struct TransferOptions {
+    chunk_size: usize,
+    progress: Option<ProgressCallback>,
+    cancellation_token: Option<CancellationToken>,
+    resume: bool,
+    verify: Option<VerifyOptions>,
+}
+
+impl fmt::Debug for TransferOptions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TransferOptions")
+            .field("chunk_size", &self.chunk_size)
+            .field("progress", &self.progress.is_some())
+            .field("cancellation_token", &self.cancellation_token)
+            .field("resume", &self.resume)
+            .field("verify", &self.verify)
+            .finish()
+    }
+}
+
+impl Default for TransferOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferOptions {
+    pub fn new() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            progress: None,
+            cancellation_token: None,
+            resume: false,
+            verify: None,
+        }
+    }
+
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    // called after every chunk and after every completed file
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&TransferProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    // continue partial files instead of starting over. only the
+    // `*_with_options` methods of `FtpClient` and `FtpPool` can resume, the
+    // plain `download_file` and `download_directory` always start over and
+    // delete partial files when they fail.
+    //
+    // downloads are written to `<name>.part` and continued with REST. the
+    // remote size and modification time are recorded in `<name>.part.json`
+    // when the partial file is created, and it is only continued while the
+    // remote file still matches them. local files are skipped when both
+    // their size and modification time match the remote file.
+    //
+    // uploads are continued with APPE from the size of the remote file. with
+    // `VerifyOptions::hash` set the remote part is also read back first and
+    // only continued, or skipped when complete, if it matches the start of
+    // the local file.
+    pub fn resume(mut self, resume: bool) -> Self {
+        self.resume = resume;
+        self
+    }
+
+    // checks every transferred file against the remote side, a mismatch
+    // fails the transfer
+    pub fn verify(mut self, verify: VerifyOptions) -> Self {
+        self.verify = Some(verify);
+        self
+    }
+
+    pub fn is_resume(&self) -> bool {
+        self.resume
+    }
+
+    pub(super) fn is_cancelled(&self) -> bool {
+        self.cancellation_token
+            .as_ref()
+            .is_some_and(|x| x.is_cancelled())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct TransferItem {
+    pub remote: String,
+    pub local: PathBuf,
+    pub size: u64,
+    // applied to downloaded files
+    pub modified: Option<SystemTime>,
+}
+
+// directories are created before any file is transferred
+#[derive(Clone, Debug, Default)]
+pub(super) struct TransferPlan {
+    pub remote_directories: Vec<String>,
+    pub local_directories: Vec<PathBuf>,
+    pub files: Vec<TransferItem>,
+    // set when the parent directories of uploaded files are known to exist
+    pub skip_parent_creation: bool,
+}
+
+struct Transfer<'a> {
+    options: &'a TransferOptions,
+    progress: TransferProgress,
+    started: Instant,
+    resumed_bytes: u64,
+}
+
+impl<'a> Transfer<'a> {
+    fn new(options: &'a TransferOptions, files: &[TransferItem]) -> Self {
+        Self {
+            options,
+            progress: TransferProgress {
+                bytes_total: files.iter().map(|x| x.size).sum(),
+                files_total: files.len(),
+                ..Default::default()
+            },
+            started: Instant::now(),
+            resumed_bytes: 0,
+        }
+    }
+
+    fn check_cancelled(&self) -> GenericResult<()> {
+        match self.options.is_cancelled() {
+            true => Err(TransferCancelled.into()),
+            false => Ok(()),
+        }
+    }
+
+    fn start_file(&mut self, item: &TransferItem) {
+        self.progress.current_file = item.remote.clone();
+        self.progress.file_bytes_done = 0;
+        self.progress.file_bytes_total = item.size;
+        self.report();
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.progress.file_bytes_done += bytes;
+        self.progress.bytes_done += bytes;
+        self.report();
+    }
+
+    // counts bytes that were transferred by an earlier attempt, they are
+    // excluded from the throughput
+    fn resume_from(&mut self, bytes: u64) {
+        self.resumed_bytes += bytes;
+        self.advance(bytes);
+    }
+
+    fn finish_file(&mut self) {
+        self.progress.files_done += 1;
+        self.report();
+    }
+
+    fn report(&mut self) {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.progress.bytes_per_second =
+                (self.progress.bytes_done - self.resumed_bytes) as f64 / elapsed;
+        }
+        if let Some(callback) = &self.options.progress {
+            callback(&self.progress);
+        }
+    }
+}
+
+// breadth-first walk of a remote directory tree that does no I/O itself. the
+// blocking and async clients list each directory it hands out and pass the
+// entries back, so both of them plan paths the same way.
+#[derive(Debug)]
+pub(super) struct RemoteWalk {
+    root: FTPPath,
+    pending: VecDeque<Vec<String>>,
+    directories: Vec<Vec<String>>,
+    files: Vec<(Vec<String>, u64, SystemTime)>,
+}
+
+impl RemoteWalk {
+    pub fn new(remote_dir: &str) -> Self {
+        Self {
+            root: FTPPath::from_str(remote_dir),
+            pending: VecDeque::from([Vec::new()]),
+            directories: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    // the next directory to list as its path relative to the root and its
+    // remote path
+    pub fn next_directory(&mut self) -> Option<(Vec<String>, String)> {
+        let relative = self.pending.pop_front()?;
+        let remote = self.remote_path(&relative);
+        self.directories.push(relative.clone());
+        Some((relative, remote))
+    }
+
+    pub fn add_entries(&mut self, relative: &[String], entries: Vec<suppaftp::list::File>) {
+        for entry in entries {
+            let mut path = relative.to_vec();
+            path.push(entry.name().to_string());
+            if entry.is_directory() {
+                self.pending.push_back(path);
+            } else if entry.is_file() {
+                self.files
+                    .push((path, entry.size() as u64, entry.modified()));
+            }
+        }
+    }
+
+    pub fn add_to_download_plan(&self, local_dir: &Path, plan: &mut TransferPlan) {
+        let local_path = |relative: &[String]| {
+            relative
+                .iter()
+                .fold(local_dir.to_path_buf(), |path, x| path.join(x))
+        };
+        plan.local_directories
+            .extend(self.directories.iter().map(|x| local_path(x)));
+        plan.files.extend(
+            self.files
+                .iter()
+                .map(|(relative, size, modified)| TransferItem {
+                    remote: self.remote_path(relative),
+                    local: local_path(relative),
+                    size: *size,
+                    modified: Some(*modified),
+                }),
+        );
+    }
+
+    // remote files, then remote directories with children before parents
+    pub fn delete_order(&self) -> (Vec<String>, Vec<String>) {
+        (
+            self.files
+                .iter()
+                .map(|(relative, _, _)| self.remote_path(relative))
+                .collect(),
+            self.directories
+                .iter()
+                .rev()
+                .map(|x| self.remote_path(x))
+                .collect(),
+        )
+    }
+
+    fn remote_path(&self, relative: &[String]) -> String {
+        let mut path = self.root.clone();
+        for part in relative {
+            path.push(part);
+        }
+        path.to_string()
+    }
+}
+
+pub(super) fn part_path(local_path: &Path) -> PathBuf {
+    let mut file_name = local_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".part");
+    local_path.with_file_name(file_name)
+}
+
+pub(super) fn plan_download_file(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_file: &str,
+    local_file: &Path,
+) -> GenericResult<TransferPlan> {
+    let remote_path = FTPPath::from_str(remote_file).to_string();
+    let (size, modified) = match super::list_entry(ftp_stream, &remote_path)? {
+        Some(x) if x.is_file() => (x.size() as u64, x.modified()),
+        _ => {
+            error!("Transfer: Remote file '{}' does not exist.", remote_path);
+            return Err(format!("Remote file '{}' does not exist.", remote_path).into());
+        }
+    };
+    Ok(TransferPlan {
+        files: vec![TransferItem {
+            remote: remote_path,
+            local: local_file.to_path_buf(),
+            size,
+            modified: Some(modified),
+        }],
+        ..Default::default()
+    })
+}
+
+pub(super) fn plan_download_directory(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_dir: &str,
+    local_dir: &Path,
+    plan: &mut TransferPlan,
+) -> GenericResult<()> {
+    let mut walk = RemoteWalk::new(remote_dir);
+    while let Some((relative, remote)) = walk.next_directory() {
+        walk.add_entries(
+            &relative,
+            super::list_directory_contents(ftp_stream, &remote)?,
+        );
+    }
+    walk.add_to_download_plan(local_dir, plan);
+    Ok(())
+}
+
+pub(super) fn plan_upload_file(
+    local_file: &Path,
+    remote_file: &str,
+) -> GenericResult<TransferPlan> {
+    Ok(TransferPlan {
+        files: vec![TransferItem {
+            remote: FTPPath::from_str(remote_file).to_string(),
+            local: local_file.to_path_buf(),
+            size: std::fs::metadata(local_file)?.len(),
+            modified: None,
+        }],
+        ..Default::default()
+    })
+}
+
+pub(super) fn plan_upload_directory(
+    local_dir: &Path,
+    remote_dir: &str,
+    plan: &mut TransferPlan,
+) -> GenericResult<()> {
+    plan.remote_directories
+        .push(FTPPath::from_str(remote_dir).to_string());
+    let mut remote_path = FTPPath::from_str(remote_dir);
+    for entry in std::fs::read_dir(local_dir)? {
+        let path = entry?.path();
+        let file_name = match path.file_name().and_then(|x| x.to_str()) {
+            Some(x) => x.to_string(),
+            None => {
+                error!(
+                    "Transfer: Failed to get file name for path '{}'",
+                    path.display()
+                );
+                return Err(
+                    format!("Failed to get file name for path '{}'.", path.display()).into(),
+                );
+            }
+        };
+        remote_path.push(&file_name);
+        if path.is_dir() {
+            plan_upload_directory(&path, &remote_path.to_string(), plan)?;
+        } else {
+            plan.files.push(TransferItem {
+                remote: remote_path.to_string(),
+                size: std::fs::metadata(&path)?.len(),
+                local: path,
+                modified: None,
+            });
+        }
+        remote_path.pop();
+    }
+    Ok(())
+}
+
+// files that completed before a cancellation are kept, the file that was in
+// progress is removed so no partial file is left behind unless resuming
+pub(super) fn download(
+    ftp_stream: &mut suppaftp::FtpStream,
+    plan: &TransferPlan,
+    options: &TransferOptions,
+) -> GenericResult<()> {
+    let mut transfer = Transfer::new(options, &plan.files);
+    for directory in &plan.local_directories {
+        std::fs::create_dir_all(directory)?;
+    }
+    for item in &plan.files {
+        transfer.check_cancelled()?;
+        transfer.start_file(item);
+        if options.resume
+            && has_size(&item.local, item.size)
+            && has_modified(&item.local, item.modified)
+        {
+            debug!("Transfer: '{}' was already downloaded", item.remote);
+            transfer.resume_from(item.size);
+            transfer.finish_file();
+            continue;
+        }
+        let part_file = part_path(&item.local);
+        if let Err(err) = download_item(ftp_stream, item, &part_file, &mut transfer) {
+            if !options.resume {
+                remove_part(&part_file)?;
+            }
+            return Err(err);
+        }
+        if let Some(x) = &options.verify {
+            verify::require_match(ftp_stream, &item.local, &item.remote, x)?;
+        }
+        transfer.finish_file();
+    }
+    Ok(())
+}
+
+fn download_item(
+    ftp_stream: &mut suppaftp::FtpStream,
+    item: &TransferItem,
+    part_file: &Path,
+    transfer: &mut Transfer,
+) -> GenericResult<()> {
+    debug!("Transfer: Downloading '{}'", item.remote);
+    create_parent_directories(&item.local)?;
+    if item.local.is_dir() {
+        return Err("Destination already exists and is a directory.".into());
+    }
+    let offset = match transfer.options.resume && part_file.is_file() {
+        true => {
+            let partial_size = std::fs::metadata(part_file)?.len();
+            if partial_size > item.size {
+                warn!(
+                    "Transfer: Partial file '{}' is larger than '{}', downloading it again",
+                    part_file.display(),
+                    item.remote
+                );
+                0
+            } else if PartialDownload::read(part_file) != Some(PartialDownload::new(item)) {
+                warn!(
+                    "Transfer: Partial file '{}' is from another version of '{}', downloading it again",
+                    part_file.display(),
+                    item.remote
+                );
+                0
+            } else {
+                partial_size
+            }
+        }
+        false => 0,
+    };
+    let mut file = match offset {
+        0 => {
+            let file = std::fs::File::create(part_file)?;
+            PartialDownload::new(item).write(part_file)?;
+            file
+        }
+        _ => std::fs::OpenOptions::new().append(true).open(part_file)?,
+    };
+    transfer.resume_from(offset);
+    if offset == 0 || offset < item.size {
+        if offset > 0 {
+            debug!(
+                "Transfer: Resuming download of '{}' at {} bytes",
+                item.remote, offset
+            );
+            ftp_stream.resume_transfer(offset as usize)?;
+        }
+        let mut data_stream = ftp_stream.retr_as_stream(&item.remote)?;
+        let mut buf: Vec<u8> = vec![0; transfer.options.chunk_size];
+        loop {
+            if transfer.options.is_cancelled() {
+                warn!("Transfer: Download of '{}' was cancelled", item.remote);
+                // ABOR closes the data connection and reads the server's
+                // replies so the control connection can still be used
+                ftp_stream.abort(data_stream)?;
+                return Err(TransferCancelled.into());
+            }
+            let bytes_read = data_stream.read(&mut buf)?;
+            if bytes_read == 0 {
+                break;
+            }
+            file.write_all(&buf[..bytes_read])?;
+            transfer.advance(bytes_read as u64);
+        }
+        ftp_stream.finalize_retr_stream(data_stream)?;
+    }
+    file.sync_all()?;
+    drop(file);
+    let downloaded_size = std::fs::metadata(part_file)?.len();
+    if downloaded_size != item.size {
+        error!(
+            "Transfer: Downloaded {} bytes of '{}' but the remote file has {} bytes.",
+            downloaded_size, item.remote, item.size
+        );
+        return Err(format!(
+            "Downloaded {} bytes of '{}' but the remote file has {} bytes.",
+            downloaded_size, item.remote, item.size
+        )
+        .into());
+    }
+    if item.local.is_file() {
+        std::fs::remove_file(&item.local)?;
+    }
+    std::fs::rename(part_file, &item.local)?;
+    remove_part(part_file)?;
+    set_modified(&item.local, item.modified)?;
+    Ok(())
+}
+
+// what a partial download was started from, kept next to the `.part` file
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct PartialDownload {
+    remote_size: u64,
+    remote_modified: Option<SystemTime>,
+}
+
+impl PartialDownload {
+    fn new(item: &TransferItem) -> Self {
+        Self {
+            remote_size: item.size,
+            remote_modified: item.modified,
+        }
+    }
+
+    // a missing or unreadable record never matches, so the download starts
+    // over
+    fn read(part_file: &Path) -> Option<Self> {
+        let bytes = std::fs::read(partial_download_path(part_file)).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn write(&self, part_file: &Path) -> GenericResult<()> {
+        std::fs::write(partial_download_path(part_file), serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+}
+
+fn partial_download_path(part_file: &Path) -> PathBuf {
+    let mut file_name = part_file.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".json");
+    part_file.with_file_name(file_name)
+}
+
+fn remove_part(part_file: &Path) -> GenericResult<()> {
+    for path in [part_file.to_path_buf(), partial_download_path(part_file)] {
+        if path.is_file() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+// files that completed before a cancellation are kept, the file that was in
+// progress is deleted from the console so no partial file is left behind
+// unless resuming
+pub(super) fn upload(
+    ftp_stream: &mut suppaftp::FtpStream,
+    plan: &TransferPlan,
+    options: &TransferOptions,
+) -> GenericResult<()> {
+    let mut transfer = Transfer::new(options, &plan.files);
+    for directory in &plan.remote_directories {
+        super::create_directory(ftp_stream, directory)?;
+    }
+    for item in &plan.files {
+        transfer.check_cancelled()?;
+        transfer.start_file(item);
+        if !plan.skip_parent_creation {
+            if let Some(x) = FTPPath::from_str(&item.remote).parent_string() {
+                super::create_directory(ftp_stream, &x)?;
+            }
+        }
+        upload_item(ftp_stream, item, &mut transfer)?;
+        if let Some(x) = &options.verify {
+            verify::require_match(ftp_stream, &item.local, &item.remote, x)?;
+        }
+        transfer.finish_file();
+    }
+    Ok(())
+}
+
+fn upload_item(
+    ftp_stream: &mut suppaftp::FtpStream,
+    item: &TransferItem,
+    transfer: &mut Transfer,
+) -> GenericResult<()> {
+    debug!("Transfer: Uploading '{}'", item.remote);
+    let offset = match transfer.options.resume {
+        true => match remote_size(ftp_stream, &item.remote)? {
+            Some(x) if x > item.size => {
+                warn!(
+                    "Transfer: Remote file '{}' is larger than '{}', uploading it again",
+                    item.remote,
+                    item.local.display()
+                );
+                0
+            }
+            Some(x)
+                if x > 0
+                    && transfer
+                        .options
+                        .verify
+                        .as_ref()
+                        .is_some_and(|y| y.is_hash())
+                    && !remote_has_prefix(ftp_stream, item, x)? =>
+            {
+                warn!(
+                    "Transfer: Remote file '{}' does not match the start of '{}', uploading it again",
+                    item.remote,
+                    item.local.display()
+                );
+                0
+            }
+            Some(x) => x,
+            None => 0,
+        },
+        false => 0,
+    };
+    if offset > 0 && offset == item.size {
+        debug!("Transfer: '{}' was already uploaded", item.remote);
+        transfer.resume_from(offset);
+        return Ok(());
+    }
+    let mut file = std::fs::File::open(&item.local)?;
+    file.seek(SeekFrom::Start(offset))?;
+    transfer.resume_from(offset);
+    let mut data_stream = match offset {
+        0 => ftp_stream.put_with_stream(&item.remote)?,
+        _ => {
+            debug!(
+                "Transfer: Resuming upload of '{}' at {} bytes",
+                item.remote, offset
+            );
+            ftp_stream.append_with_stream(&item.remote)?
+        }
+    };
+    let mut buf: Vec<u8> = vec![0; transfer.options.chunk_size];
+    let mut bytes_written: u64 = offset;
+    loop {
+        if transfer.options.is_cancelled() {
+            warn!("Transfer: Upload of '{}' was cancelled", item.remote);
+            ftp_stream.finalize_put_stream(data_stream)?;
+            if !transfer.options.resume {
+                super::delete_file(ftp_stream, &item.remote)?;
+            }
+            return Err(TransferCancelled.into());
+        }
+        let bytes_read = file.read(&mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        data_stream.write_all(&buf[..bytes_read])?;
+        bytes_written += bytes_read as u64;
+        transfer.advance(bytes_read as u64);
+    }
+    data_stream.flush()?;
+    ftp_stream.finalize_put_stream(data_stream)?;
+    if bytes_written != item.size {
+        error!(
+            "Transfer: Expected to write {} bytes to remote but wrote {}.",
+            item.size, bytes_written
+        );
+        return Err(format!(
+            "Expected to write {} bytes to remote but wrote {}.",
+            item.size, bytes_written
+        )
+        .into());
+    }
+    if offset > 0 {
+        let uploaded_size = remote_size(ftp_stream, &item.remote)?;
+        if uploaded_size != Some(item.size) {
+            error!(
+                "Transfer: Remote file '{}' has {:?} bytes after resuming but {} were expected.",
+                item.remote, uploaded_size, item.size
+            );
+            return Err(format!(
+                "Remote file '{}' has {:?} bytes after resuming but {} were expected.",
+                item.remote, uploaded_size, item.size
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn remote_size(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_file: &str,
+) -> GenericResult<Option<u64>> {
+    match super::list_entry(ftp_stream, remote_file)? {
+        Some(x) if x.is_file() => Ok(Some(x.size() as u64)),
+        _ => Ok(None),
+    }
+}
+
+fn has_size(local_path: &Path, size: u64) -> bool {
+    std::fs::metadata(local_path).is_ok_and(|x| x.is_file() && x.len() == size)
+}
+
+// completed downloads are stamped with the remote modification time, so both
+// sides come from the console's clock and only the local file system's
+// precision has to be allowed for
+fn has_modified(local_path: &Path, modified: Option<SystemTime>) -> bool {
+    let modified = match modified {
+        Some(x) => x,
+        None => return false,
+    };
+    std::fs::metadata(local_path)
+        .and_then(|x| x.modified())
+        .is_ok_and(|x| {
+            let difference = match x.duration_since(modified) {
+                Ok(y) => y,
+                Err(e) => e.duration(),
+            };
+            difference <= RESUME_MTIME_TOLERANCE
+        })
+}
+
+fn set_modified(local_path: &Path, modified: Option<SystemTime>) -> GenericResult<()> {
+    if let Some(x) = modified {
+        std::fs::File::options()
+            .write(true)
+            .open(local_path)?
+            .set_modified(x)?;
+    }
+    Ok(())
+}
+
+// the console cannot hash files, so the remote part is read back and compared
+// with the start of the local file
+fn remote_has_prefix(
+    ftp_stream: &mut suppaftp::FtpStream,
+    item: &TransferItem,
+    len: u64,
+) -> GenericResult<bool> {
+    debug!(
+        "Transfer: Comparing the first {} bytes of '{}' with '{}'",
+        len,
+        item.remote,
+        item.local.display()
+    );
+    let mut file = std::fs::File::open(&item.local)?.take(len);
+    let local = verify::sha256(&mut file)?;
+    Ok(verify::remote_sha256(ftp_stream, &item.remote)? == local)
+}
@@ -0,0 +1,563 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use super::FTPPath;
+use crate::utils::{create_parent_directories, GenericResult};
+use log::{debug, error, warn};
+use sha2::{Digest, Sha256};
+
+// how the size of a remote file is determined. `Auto` uses SIZE and falls
+// back to LIST for servers that do not support it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SizeMethod {
+    Size,
+    List,
+    Auto,
+}
+
+#[derive(Clone, Debug)]
+pub struct VerifyOptions {
+    size_method: SizeMethod,
+    hash: bool,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VerifyOptions {
+    pub fn new() -> Self {
+        Self {
+            size_method: SizeMethod::Auto,
+            hash: false,
+        }
+    }
+
+    pub fn size_method(mut self, size_method: SizeMethod) -> Self {
+        self.size_method = size_method;
+        self
+    }
+
+    // reads the remote file back and compares its SHA-256 with the local file
+    pub fn hash(mut self, hash: bool) -> Self {
+        self.hash = hash;
+        self
+    }
+
+    pub(super) fn is_hash(&self) -> bool {
+        self.hash
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Verification {
+    pub remote: String,
+    pub local: PathBuf,
+    pub remote_size: u64,
+    pub local_size: u64,
+    pub remote_sha256: Option<String>,
+    pub local_sha256: Option<String>,
+}
+
+impl Verification {
+    pub fn is_match(&self) -> bool {
+        self.remote_size == self.local_size && self.remote_sha256 == self.local_sha256
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "'{}' has {} bytes and '{}' has {} bytes",
+            self.remote,
+            self.remote_size,
+            self.local.display(),
+            self.local_size
+        )?;
+        if let (Some(remote), Some(local)) = (&self.remote_sha256, &self.local_sha256) {
+            write!(f, ", SHA-256 {} and {}", remote, local)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn local_sha256(local_file: &Path) -> GenericResult<String> {
+    let mut file = std::fs::File::open(local_file)?;
+    sha256(&mut file)
+}
+
+pub(super) fn sha256(reader: &mut dyn Read) -> GenericResult<String> {
+    let mut hasher = Sha256::new();
+    let mut buf: Vec<u8> = vec![0; 64 * 1024];
+    loop {
+        let bytes_read = reader.read(&mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buf[..bytes_read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub(super) fn remote_sha256(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_file: &str,
+) -> GenericResult<String> {
+    debug!("Verify: Reading back '{}'", remote_file);
+    let mut data_stream = ftp_stream.retr_as_stream(FTPPath::from_str(remote_file).to_string())?;
+    let result = sha256(&mut data_stream);
+    ftp_stream.finalize_retr_stream(data_stream)?;
+    result
+}
+
+pub(super) fn remote_size(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_file: &str,
+    method: SizeMethod,
+) -> GenericResult<u64> {
+    let remote_path = FTPPath::from_str(remote_file).to_string();
+    if method != SizeMethod::List {
+        match ftp_stream.size(&remote_path) {
+            Ok(x) => return Ok(x as u64),
+            Err(err) if method == SizeMethod::Size => return Err(err.into()),
+            Err(err) => debug!(
+                "Verify: SIZE failed for '{}', falling back to LIST. Got the following error: {}",
+                remote_path, err
+            ),
+        }
+    }
+    match super::list_entry(ftp_stream, &remote_path)? {
+        Some(x) if x.is_file() => Ok(x.size() as u64),
+        _ => {
+            error!("Verify: Remote file '{}' does not exist.", remote_path);
+            Err(format!("Remote file '{}' does not exist.", remote_path).into())
+        }
+    }
+}
+
+pub(super) fn verify(
+    ftp_stream: &mut suppaftp::FtpStream,
+    local_file: &Path,
+    remote_file: &str,
+    options: &VerifyOptions,
+) -> GenericResult<Verification> {
+    let local_size = std::fs::metadata(local_file)?.len();
+    let remote_size = remote_size(ftp_stream, remote_file, options.size_method)?;
+    // hashing is skipped when the sizes already differ
+    let (remote_sha256, local_sha256) = match options.hash && local_size == remote_size {
+        true => (
+            Some(remote_sha256(ftp_stream, remote_file)?),
+            Some(local_sha256(local_file)?),
+        ),
+        false => (None, None),
+    };
+    Ok(Verification {
+        remote: FTPPath::from_str(remote_file).to_string(),
+        local: local_file.to_path_buf(),
+        remote_size,
+        local_size,
+        remote_sha256,
+        local_sha256,
+    })
+}
+
+// used by transfers, fails unless both sides match
+pub(super) fn require_match(
+    ftp_stream: &mut suppaftp::FtpStream,
+    local_file: &Path,
+    remote_file: &str,
+    options: &VerifyOptions,
+) -> GenericResult<()> {
+    let verification = verify(ftp_stream, local_file, remote_file, options)?;
+    if !verification.is_match() {
+        error!("Verify: Verification failed, {}.", verification);
+        return Err(format!("Verification failed, {}.", verification).into());
+    }
+    debug!("Verify: Verified '{}'", verification.remote);
+    Ok(())
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum ManifestMismatch {
+    Missing(String),
+    Unexpected(String),
+    SizeChanged {
+        path: String,
+        expected: u64,
+        actual: u64,
+    },
+    HashChanged {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for ManifestMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ManifestMismatch::Missing(x) => write!(f, "missing    {}", x),
+            ManifestMismatch::Unexpected(x) => write!(f, "unexpected {}", x),
+            ManifestMismatch::SizeChanged {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "size       {} (expected {} bytes, got {})",
+                path, expected, actual
+            ),
+            ManifestMismatch::HashChanged {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "sha256     {} (expected {}, got {})",
+                path, expected, actual
+            ),
+        }
+    }
+}
+
+// sizes and SHA-256 hashes of the files in a directory, keyed by their path
+// relative to the directory using `/` separators. paths are matched without
+// regard to case when verifying, since FATX ignores case.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Manifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn from_local_dir(local_dir: &Path) -> GenericResult<Self> {
+        let mut manifest = Self::default();
+        for (relative, path) in local_files(local_dir)? {
+            manifest.files.insert(
+                relative,
+                ManifestEntry {
+                    size: std::fs::metadata(&path)?.len(),
+                    sha256: local_sha256(&path)?,
+                },
+            );
+        }
+        Ok(manifest)
+    }
+
+    pub(super) fn from_remote_dir(
+        ftp_stream: &mut suppaftp::FtpStream,
+        remote_dir: &str,
+    ) -> GenericResult<Self> {
+        let mut manifest = Self::default();
+        for (relative, path, size) in remote_files(ftp_stream, remote_dir)? {
+            manifest.files.insert(
+                relative,
+                ManifestEntry {
+                    size,
+                    sha256: remote_sha256(ftp_stream, &path)?,
+                },
+            );
+        }
+        Ok(manifest)
+    }
+
+    pub fn load(file_path: &Path) -> GenericResult<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(file_path)?)?)
+    }
+
+    pub fn save(&self, file_path: &Path) -> GenericResult<()> {
+        create_parent_directories(file_path)?;
+        std::fs::write(file_path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.files.values().map(|x| x.size).sum()
+    }
+
+    pub fn verify_local_dir(&self, local_dir: &Path) -> GenericResult<Vec<ManifestMismatch>> {
+        let mut actual = fold_paths(local_files(local_dir)?);
+        let mut mismatches = Vec::new();
+        for (relative, expected) in &self.files {
+            let path = match take_match(&mut actual, relative) {
+                Some(x) => x,
+                None => {
+                    mismatches.push(ManifestMismatch::Missing(relative.clone()));
+                    continue;
+                }
+            };
+            let size = std::fs::metadata(&path)?.len();
+            if size != expected.size {
+                mismatches.push(ManifestMismatch::SizeChanged {
+                    path: relative.clone(),
+                    expected: expected.size,
+                    actual: size,
+                });
+                continue;
+            }
+            let sha256 = local_sha256(&path)?;
+            if sha256 != expected.sha256 {
+                mismatches.push(ManifestMismatch::HashChanged {
+                    path: relative.clone(),
+                    expected: expected.sha256.clone(),
+                    actual: sha256,
+                });
+            }
+        }
+        mismatches.extend(unexpected(actual));
+        Ok(mismatches)
+    }
+
+    // sizes are taken from the directory listing, hashes are only compared
+    // when `hash` is set since that reads every file back
+    pub(super) fn verify_remote_dir(
+        &self,
+        ftp_stream: &mut suppaftp::FtpStream,
+        remote_dir: &str,
+        hash: bool,
+    ) -> GenericResult<Vec<ManifestMismatch>> {
+        let files = remote_files(ftp_stream, remote_dir)?
+            .into_iter()
+            .map(|(relative, path, size)| (relative, (path, size)))
+            .collect();
+        let mut actual = fold_paths(files);
+        let mut mismatches = Vec::new();
+        for (relative, expected) in &self.files {
+            let (path, size) = match take_match(&mut actual, relative) {
+                Some(x) => x,
+                None => {
+                    mismatches.push(ManifestMismatch::Missing(relative.clone()));
+                    continue;
+                }
+            };
+            if size != expected.size {
+                mismatches.push(ManifestMismatch::SizeChanged {
+                    path: relative.clone(),
+                    expected: expected.size,
+                    actual: size,
+                });
+                continue;
+            }
+            if hash {
+                let sha256 = remote_sha256(ftp_stream, &path)?;
+                if sha256 != expected.sha256 {
+                    mismatches.push(ManifestMismatch::HashChanged {
+                        path: relative.clone(),
+                        expected: expected.sha256.clone(),
+                        actual: sha256,
+                    });
+                }
+            }
+        }
+        mismatches.extend(unexpected(actual));
+        Ok(mismatches)
+    }
+}
+
+type FoldedFiles<T> = BTreeMap<String, Vec<(String, T)>>;
+
+// groups files by their lowercased path
+fn fold_paths<T>(files: Vec<(String, T)>) -> FoldedFiles<T> {
+    let mut folded: FoldedFiles<T> = BTreeMap::new();
+    for (relative, x) in files {
+        folded
+            .entry(relative.to_lowercase())
+            .or_default()
+            .push((relative, x));
+    }
+    for candidates in folded.values_mut() {
+        candidates.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    folded
+}
+
+// removes the file matching `relative`, preferring the one spelled the same
+fn take_match<T>(folded: &mut FoldedFiles<T>, relative: &str) -> Option<T> {
+    let candidates = folded.get_mut(&relative.to_lowercase())?;
+    let index = candidates
+        .iter()
+        .position(|(x, _)| x == relative)
+        .unwrap_or(0);
+    if index >= candidates.len() {
+        return None;
+    }
+    Some(candidates.remove(index).1)
+}
+
+fn unexpected<T>(folded: FoldedFiles<T>) -> impl Iterator<Item = ManifestMismatch> {
+    folded
+        .into_values()
+        .flatten()
+        .map(|(relative, _)| ManifestMismatch::Unexpected(relative))
+}
+
+fn local_files(local_dir: &Path) -> GenericResult<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    let mut directories = vec![(String::new(), local_dir.to_path_buf())];
+    while let Some((prefix, directory)) = directories.pop() {
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let name = match path.file_name().and_then(|x| x.to_str()) {
+                Some(x) => x.to_string(),
+                None => {
+                    warn!(
+                        "Verify: Skipping '{}' because its name is not valid UTF-8",
+                        path.display()
+                    );
+                    continue;
+                }
+            };
+            let relative = match prefix.is_empty() {
+                true => name,
+                false => format!("{}/{}", prefix, name),
+            };
+            match path.is_dir() {
+                true => directories.push((relative, path)),
+                false => files.push((relative, path)),
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn remote_files(
+    ftp_stream: &mut suppaftp::FtpStream,
+    remote_dir: &str,
+) -> GenericResult<Vec<(String, String, u64)>> {
+    let mut files = Vec::new();
+    let mut directories = vec![(String::new(), FTPPath::from_str(remote_dir))];
+    while let Some((prefix, directory)) = directories.pop() {
+        for entry in super::list_directory_contents(ftp_stream, &directory.to_string())? {
+            let relative = match prefix.is_empty() {
+                true => entry.name().to_string(),
+                false => format!("{}/{}", prefix, entry.name()),
+            };
+            let path = directory.join(entry.name());
+            if entry.is_directory() {
+                directories.push((relative, path));
+            } else if entry.is_file() {
+                files.push((relative, path.to_string(), entry.size() as u64));
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "libaustralis-verify-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(dir: &Path, relative: &str, contents: &str) {
+        let path = dir.join(relative);
+        create_parent_directories(&path).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn manifest_round_trip() {
+        let dir = temp_dir("round-trip");
+        write(&dir, "data/Save.dat", "save");
+        write(&dir, "top.txt", "top");
+        let manifest = Manifest::from_local_dir(&dir.join("data")).unwrap();
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files["Save.dat"].size, 4);
+        assert_eq!(
+            manifest.files["Save.dat"].sha256,
+            local_sha256(&dir.join("data/Save.dat")).unwrap()
+        );
+        let manifest = Manifest::from_local_dir(&dir).unwrap();
+        let file = dir.join("manifests/manifest.json");
+        manifest.save(&file).unwrap();
+        let loaded = Manifest::load(&file).unwrap();
+        assert_eq!(loaded.files, manifest.files);
+        assert_eq!(loaded.total_size(), 7);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn verify_local_dir_reports_mismatches() {
+        let dir = temp_dir("mismatches");
+        write(&dir, "Content/Same.bin", "same");
+        write(&dir, "Content/Size.bin", "size");
+        write(&dir, "Content/Hash.bin", "hash");
+        write(&dir, "Content/Gone.bin", "gone");
+        let manifest = Manifest::from_local_dir(&dir).unwrap();
+        assert!(manifest.verify_local_dir(&dir).unwrap().is_empty());
+        write(&dir, "Content/Size.bin", "longer");
+        write(&dir, "Content/Hash.bin", "HASH");
+        std::fs::remove_file(dir.join("Content/Gone.bin")).unwrap();
+        write(&dir, "Content/New.bin", "new");
+        let mut mismatches: Vec<String> = manifest
+            .verify_local_dir(&dir)
+            .unwrap()
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        mismatches.sort();
+        assert_eq!(mismatches.len(), 4);
+        assert!(mismatches[0].starts_with("missing    Content/Gone.bin"));
+        assert!(mismatches[1].starts_with("sha256     Content/Hash.bin"));
+        assert!(mismatches[2].starts_with("size       Content/Size.bin"));
+        assert!(mismatches[3].starts_with("unexpected Content/New.bin"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn verify_local_dir_ignores_case() {
+        let dir = temp_dir("case");
+        write(&dir, "Content/Save.DAT", "save");
+        let manifest = Manifest::from_local_dir(&dir).unwrap();
+        std::fs::rename(dir.join("Content"), dir.join("content")).unwrap();
+        std::fs::rename(dir.join("content/Save.DAT"), dir.join("content/save.dat")).unwrap();
+        assert!(manifest.verify_local_dir(&dir).unwrap().is_empty());
+        // without an exact match the first spelling in path order is
+        // compared, the other one is unexpected whatever the listing order
+        write(&dir, "content/Save.DAT", "save");
+        let mismatches = manifest.verify_local_dir(&dir).unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert!(
+            matches!(&mismatches[0], ManifestMismatch::Unexpected(x) if x == "content/save.dat")
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -21,9 +21,80 @@
 // TODO improve logging
 // TODO improve error handling/bubbling
 // TODO verify response codes
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::aurora::device_path::DevicePath;
 use crate::aurora::http_schemas;
 use crate::utils::GenericResult;
-use log::error;
+use log::{debug, error};
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+const LAUNCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// the `type` Aurora's web UI sends to `/title/launch` for each kind of
+// executable. other values are passed through as `Unknown`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ExecType {
+    Xex,
+    XexContainer,
+    Xbe,
+    XbeContainer,
+    Unknown(u32),
+}
+
+impl fmt::Display for ExecType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecType::Xex => write!(f, "Xex"),
+            ExecType::XexContainer => write!(f, "XexContainer"),
+            ExecType::Xbe => write!(f, "Xbe"),
+            ExecType::XbeContainer => write!(f, "XbeContainer"),
+            ExecType::Unknown(x) => write!(f, "Unknown({})", x),
+        }
+    }
+}
+
+impl ExecType {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Xex,
+            1 => Self::XexContainer,
+            2 => Self::Xbe,
+            3 => Self::XbeContainer,
+            x => Self::Unknown(x),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Xex => 0,
+            Self::XexContainer => 1,
+            Self::Xbe => 2,
+            Self::XbeContainer => 3,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum LaunchOutcome {
+    // the title was seen starting after the launch request
+    Started(http_schemas::Title),
+    // the title was already running and no restart was seen. a relaunch that
+    // completes between two polls also ends up here.
+    AlreadyRunning(http_schemas::Title),
+}
+
+impl LaunchOutcome {
+    pub fn title(&self) -> &http_schemas::Title {
+        match self {
+            Self::Started(x) | Self::AlreadyRunning(x) => x,
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct HttpClient {
@@ -630,6 +701,91 @@ impl HttpClient {
         }
     }
 
+    pub async fn launch_title(
+        &self,
+        token: Option<&str>,
+        executable: &DevicePath,
+        exec_type: ExecType,
+    ) -> GenericResult<()> {
+        let (path, exec) = executable.launch_args()?;
+        self.post_title_launch(token, &path, &exec, exec_type.as_u32())
+            .await
+    }
+
+    // launches `executable` and polls `/title` until `title_id` is running.
+    // the launch counts as started once a poll fails (the console restarting),
+    // another title or path is seen, or `title_id` was not running before.
+    // when `title_id` keeps running unchanged until `timeout`, the result is
+    // `LaunchOutcome::AlreadyRunning` rather than an error.
+    pub async fn launch_and_wait(
+        &self,
+        token: Option<&str>,
+        executable: &DevicePath,
+        exec_type: ExecType,
+        title_id: u32,
+        timeout: Duration,
+    ) -> GenericResult<LaunchOutcome> {
+        let deadline = Instant::now() + timeout;
+        let previous = self.get_title(token).await.ok();
+        let mut transitioned = match &previous {
+            Some(x) => http_schemas::parse_hex_u32(&x.titleid).ok() != Some(title_id),
+            None => true,
+        };
+        self.launch_title(token, executable, exec_type).await?;
+        loop {
+            let mut running = None;
+            match self.get_title(token).await {
+                Ok(title) => {
+                    if previous.as_ref().map(|x| &x.path) != Some(&title.path) {
+                        transitioned = true;
+                    }
+                    match http_schemas::parse_hex_u32(&title.titleid) {
+                        Ok(x) if x == title_id && transitioned => {
+                            return Ok(LaunchOutcome::Started(title))
+                        }
+                        Ok(x) if x == title_id => {
+                            debug!(
+                                "Launch And Wait: Title {:08X} has not restarted yet.",
+                                title_id
+                            );
+                            running = Some(title);
+                        }
+                        _ => {
+                            transitioned = true;
+                            debug!(
+                                "Launch And Wait: Waiting for title {:08X}, current title is '{}'.",
+                                title_id, title.titleid
+                            );
+                        }
+                    }
+                }
+                Err(err) => {
+                    transitioned = true;
+                    debug!(
+                        "Launch And Wait: Failed to get title. Got the following error: {}",
+                        err
+                    );
+                }
+            }
+            if Instant::now() >= deadline {
+                if let Some(title) = running {
+                    debug!(
+                        "Launch And Wait: Title {:08X} was already running and no restart was seen.",
+                        title_id
+                    );
+                    return Ok(LaunchOutcome::AlreadyRunning(title));
+                }
+                let msg = format!(
+                    "Title {:08X} was not running {:?} after launching '{}'.",
+                    title_id, timeout, executable
+                );
+                error!("{}", msg);
+                return Err(msg.into());
+            }
+            tokio::time::sleep(LAUNCH_POLL_INTERVAL).await;
+        }
+    }
+
     pub async fn get_title_live_cache(&self, token: Option<&str>) -> GenericResult<String> {
         let resp = self.get(token, "/title/live/cache", None).await?;
         match resp.text().await {
@@ -680,3 +836,62 @@ impl HttpClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{spawn_http_stub, StubRoute};
+
+    const TITLE: &str = r#"{"disc":{"count":1,"current":1},"mediaid":"00000000","path":"\\Device\\Harddisk0\\Partition1\\Games\\Halo 3\\default.xex","resolution":{"height":720,"width":1280},"titleid":"0x4D5307E6","tuver":0,"version":{"base":"0","current":"0"}}"#;
+
+    async fn stub_console() -> HttpClient {
+        let address = spawn_http_stub(
+            vec![
+                StubRoute::new("GET", "/title", 200, TITLE),
+                StubRoute::new("POST", "/title/launch", 200, ""),
+            ],
+            None,
+        )
+        .await;
+        HttpClient::new(
+            address.ip().to_string(),
+            address.port() as usize,
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn launch_and_wait_reports_already_running_title() {
+        let client = stub_console().await;
+        let executable: DevicePath = "Hdd1:\\Games\\Halo 3\\default.xex".parse().unwrap();
+        let outcome = client
+            .launch_and_wait(
+                None,
+                &executable,
+                ExecType::Xex,
+                0x4D53_07E6,
+                Duration::from_millis(100),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LaunchOutcome::AlreadyRunning(_)));
+        assert_eq!(outcome.title().titleid, "0x4D5307E6");
+    }
+
+    #[tokio::test]
+    async fn launch_and_wait_fails_when_title_never_runs() {
+        let client = stub_console().await;
+        let executable: DevicePath = "Hdd1:\\Games\\Other\\default.xex".parse().unwrap();
+        let result = client
+            .launch_and_wait(
+                None,
+                &executable,
+                ExecType::Xex,
+                0x4156_0817,
+                Duration::from_millis(100),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
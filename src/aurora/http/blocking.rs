@@ -0,0 +1,264 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::time::Duration;
+
+use crate::aurora::device_path::DevicePath;
+use crate::aurora::http::{ExecType, LaunchOutcome};
+use crate::aurora::http_schemas;
+use crate::utils::{block_on, GenericResult};
+
+// synchronous mirror of `aurora::http::HttpClient`. each call runs the async
+// request to completion on a temporary current-thread runtime, so it must not
+// be called from within an async context.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    client: super::HttpClient,
+}
+
+impl From<super::HttpClient> for HttpClient {
+    fn from(client: super::HttpClient) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpClient {
+    pub fn new(
+        ip: String,
+        port: usize,
+        username: Option<String>,
+        password: Option<String>,
+    ) -> Self {
+        Self {
+            client: super::HttpClient::new(ip, port, username, password),
+        }
+    }
+
+    pub fn as_async(&self) -> &super::HttpClient {
+        &self.client
+    }
+
+    pub fn new_token(&self) -> GenericResult<Option<String>> {
+        block_on(self.client.new_token())
+    }
+
+    // achievement endpoints
+    pub fn get_achievement(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<Vec<http_schemas::Achievement>> {
+        block_on(self.client.get_achievement(token))
+    }
+
+    pub fn get_achievement_player(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<Vec<http_schemas::AchievementPlayer>> {
+        block_on(self.client.get_achievement_player(token))
+    }
+
+    // authenticate endpoints
+    pub fn post_authenticate(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> GenericResult<http_schemas::Authentication> {
+        block_on(self.client.post_authenticate(username, password))
+    }
+
+    // dashlaunch endpoints
+    pub fn get_dashlaunch(&self, token: Option<&str>) -> GenericResult<http_schemas::Dashlaunch> {
+        block_on(self.client.get_dashlaunch(token))
+    }
+
+    // filebrowser endpoints
+    pub fn get_filebrowser(
+        &self,
+        token: Option<&str>,
+        path: Option<&str>,
+        filter: Option<&str>,
+    ) -> GenericResult<Vec<http_schemas::FilebrowserEntry>> {
+        block_on(self.client.get_filebrowser(token, path, filter))
+    }
+
+    // image endpoints
+    pub fn get_image_achievement(&self, token: Option<&str>, uuid: &str) -> GenericResult<Vec<u8>> {
+        block_on(self.client.get_image_achievement(token, uuid))
+    }
+
+    pub fn get_image_profile(&self, token: Option<&str>, uuid: &str) -> GenericResult<Vec<u8>> {
+        block_on(self.client.get_image_profile(token, uuid))
+    }
+
+    pub fn get_image_screencapture(
+        &self,
+        token: Option<&str>,
+        uuid: &str,
+    ) -> GenericResult<Vec<u8>> {
+        block_on(self.client.get_image_screencapture(token, uuid))
+    }
+
+    // memory endpoints
+    pub fn get_memory(&self, token: Option<&str>) -> GenericResult<http_schemas::Memory> {
+        block_on(self.client.get_memory(token))
+    }
+
+    // multidisc endpoints
+    pub fn get_multidisc(&self, token: Option<&str>) -> GenericResult<http_schemas::Multidisc> {
+        block_on(self.client.get_multidisc(token))
+    }
+
+    // plugin endpoints
+    pub fn get_plugin(&self, token: Option<&str>) -> GenericResult<http_schemas::Plugin> {
+        block_on(self.client.get_plugin(token))
+    }
+
+    // profile endpoints
+    pub fn get_profile(&self, token: Option<&str>) -> GenericResult<Vec<http_schemas::Profile>> {
+        block_on(self.client.get_profile(token))
+    }
+
+    // screencapture endpoints
+    pub fn delete_screencapture(&self, token: Option<&str>, uuid: &str) -> GenericResult<()> {
+        block_on(self.client.delete_screencapture(token, uuid))
+    }
+
+    pub fn get_screencapture_meta(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<http_schemas::ScreencaptureMeta> {
+        block_on(self.client.get_screencapture_meta(token))
+    }
+
+    pub fn get_screencapture_meta_list(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<Vec<http_schemas::ScreencaptureMeta>> {
+        block_on(self.client.get_screencapture_meta_list(token))
+    }
+
+    pub fn get_screencapture_meta_list_count(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<http_schemas::ScreencaptureMetaListCount> {
+        block_on(self.client.get_screencapture_meta_list_count(token))
+    }
+
+    // smc endpoints
+    pub fn get_smc(&self, token: Option<&str>) -> GenericResult<http_schemas::Smc> {
+        block_on(self.client.get_smc(token))
+    }
+
+    // system endpoints
+    pub fn get_system(&self, token: Option<&str>) -> GenericResult<http_schemas::System> {
+        block_on(self.client.get_system(token))
+    }
+
+    // systemlink endpoints
+    pub fn get_systemlink(&self, token: Option<&str>) -> GenericResult<http_schemas::Systemlink> {
+        block_on(self.client.get_systemlink(token))
+    }
+
+    pub fn get_systemlink_bandwidth(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<http_schemas::SystemlinkBandwidth> {
+        block_on(self.client.get_systemlink_bandwidth(token))
+    }
+
+    // temperature endpoints
+    pub fn get_temperature(&self, token: Option<&str>) -> GenericResult<http_schemas::Temperature> {
+        block_on(self.client.get_temperature(token))
+    }
+
+    // thread endpoints
+    pub fn get_thread(&self, token: Option<&str>) -> GenericResult<Vec<http_schemas::Thread>> {
+        block_on(self.client.get_thread(token))
+    }
+
+    pub fn get_thread_state(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<http_schemas::ThreadState> {
+        block_on(self.client.get_thread_state(token))
+    }
+
+    pub fn post_thread_state(&self, token: Option<&str>, suspend: bool) -> GenericResult<()> {
+        block_on(self.client.post_thread_state(token, suspend))
+    }
+
+    // title endpoints
+    pub fn get_title(&self, token: Option<&str>) -> GenericResult<http_schemas::Title> {
+        block_on(self.client.get_title(token))
+    }
+
+    pub fn get_title_file(&self, token: Option<&str>, path: &str) -> GenericResult<Vec<u8>> {
+        block_on(self.client.get_title_file(token, path))
+    }
+
+    pub fn post_title_launch(
+        &self,
+        token: Option<&str>,
+        path: &str,
+        exec: &str,
+        exec_type: u32,
+    ) -> GenericResult<()> {
+        block_on(self.client.post_title_launch(token, path, exec, exec_type))
+    }
+
+    pub fn launch_title(
+        &self,
+        token: Option<&str>,
+        executable: &DevicePath,
+        exec_type: ExecType,
+    ) -> GenericResult<()> {
+        block_on(self.client.launch_title(token, executable, exec_type))
+    }
+
+    pub fn launch_and_wait(
+        &self,
+        token: Option<&str>,
+        executable: &DevicePath,
+        exec_type: ExecType,
+        title_id: u32,
+        timeout: Duration,
+    ) -> GenericResult<LaunchOutcome> {
+        block_on(
+            self.client
+                .launch_and_wait(token, executable, exec_type, title_id, timeout),
+        )
+    }
+
+    pub fn get_title_live_cache(&self, token: Option<&str>) -> GenericResult<String> {
+        block_on(self.client.get_title_live_cache(token))
+    }
+
+    pub fn post_title_live_cache(&self, token: Option<&str>, liveinfo: &str) -> GenericResult<()> {
+        block_on(self.client.post_title_live_cache(token, liveinfo))
+    }
+
+    // update endpoints
+    pub fn get_update_notification(
+        &self,
+        token: Option<&str>,
+    ) -> GenericResult<http_schemas::UpdateNotification> {
+        block_on(self.client.get_update_notification(token))
+    }
+}
@@ -16,6 +16,9 @@
  * You should have received a copy of the GNU General Public License along with
  * libaustralis. If not, see <https://www.gnu.org/licenses/>.
  */
+use std::fmt;
+
+use crate::utils::GenericResult;
 use serde;
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -361,3 +364,414 @@ pub struct UpdateNotification {
     pub screencapture: u32,
     pub title: u32,
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// typed accessors for raw status values
+////////////////////////////////////////////////////////////////////////////////
+impl FilebrowserEntry {
+    pub fn file_attributes(&self) -> FileAttributes {
+        FileAttributes::from_bits_retain(self.attributes)
+    }
+
+    pub fn is_directory(&self) -> bool {
+        self.file_attributes().contains(FileAttributes::DIRECTORY)
+    }
+}
+
+impl PluginVersionNumber {
+    pub fn release_type(&self) -> ReleaseType {
+        ReleaseType::from_u32(self.type_)
+    }
+}
+
+impl Smc {
+    pub fn av_pack(&self) -> AvPack {
+        AvPack::from_u32(self.avpack)
+    }
+
+    pub fn dvd_media_type(&self) -> DvdMediaType {
+        DvdMediaType::from_u32(self.dvdmediatype)
+    }
+
+    pub fn tilt_state(&self) -> TiltState {
+        TiltState::from_u32(self.tiltstate)
+    }
+
+    pub fn tray_state(&self) -> TrayState {
+        // the state is also seen as the SMC tray event 0x6N, where N is the
+        // state number
+        match self.traystate & 0xFFFF_FFF0 {
+            0x60 => TrayState::from_u32(self.traystate & 0x0F),
+            _ => TrayState::from_u32(self.traystate),
+        }
+    }
+}
+
+impl SystemConsole {
+    pub fn console_type(&self) -> ConsoleType {
+        ConsoleType::from(self.type_.as_str())
+    }
+}
+
+impl Thread {
+    pub fn run_state(&self) -> ThreadRunState {
+        ThreadRunState::from_u32(self.state)
+    }
+
+    pub fn thread_type(&self) -> ThreadType {
+        ThreadType::from_u32(self.type_)
+    }
+
+    pub fn thread_flags(&self) -> GenericResult<ThreadFlags> {
+        Ok(ThreadFlags::from_bits_retain(parse_hex_u32(&self.flags)?))
+    }
+}
+
+// temperatures are reported in fahrenheit unless the `celsius` flag is set
+pub(crate) fn celsius(value: f32, is_celsius: bool) -> f64 {
+    match is_celsius {
+        true => value as f64,
+        false => (value as f64 - 32.0) * 5.0 / 9.0,
+    }
+}
+
+pub(crate) fn parse_hex_u32(value: &str) -> GenericResult<u32> {
+    let trimmed = value.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    match u32::from_str_radix(digits, 16) {
+        Ok(x) => Ok(x),
+        Err(err) => Err(format!("Invalid hexadecimal value '{}': {}", value, err).into()),
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// typed status values
+////////////////////////////////////////////////////////////////////////////////
+// values follow the SMC AV pack identifiers used by libxenon
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AvPack {
+    Component,
+    Composite,
+    CompositeSvideo,
+    Hdmi,
+    HdmiAudio,
+    Scart,
+    Vga,
+    Unknown(u32),
+}
+
+impl fmt::Display for AvPack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AvPack::Component => write!(f, "Component AV"),
+            AvPack::Composite => write!(f, "Composite AV"),
+            AvPack::CompositeSvideo => write!(f, "Composite/S-Video AV"),
+            AvPack::Hdmi => write!(f, "HDMI"),
+            AvPack::HdmiAudio => write!(f, "HDMI with audio adapter"),
+            AvPack::Scart => write!(f, "SCART AV"),
+            AvPack::Vga => write!(f, "VGA"),
+            AvPack::Unknown(x) => write!(f, "Unknown AV pack (0x{:02X})", x),
+        }
+    }
+}
+
+impl AvPack {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0x0C => Self::Component,
+            0x13 => Self::HdmiAudio,
+            0x1B => Self::Vga,
+            0x1F => Self::Hdmi,
+            0x43 => Self::Composite,
+            0x47 => Self::Scart,
+            0x54 => Self::CompositeSvideo,
+            x => Self::Unknown(x),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Component => 0x0C,
+            Self::HdmiAudio => 0x13,
+            Self::Vga => 0x1B,
+            Self::Hdmi => 0x1F,
+            Self::Composite => 0x43,
+            Self::Scart => 0x47,
+            Self::CompositeSvideo => 0x54,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ConsoleType {
+    Devkit,
+    Retail,
+    Testkit,
+    Unknown(String),
+}
+
+impl fmt::Display for ConsoleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConsoleType::Devkit => write!(f, "Development kit"),
+            ConsoleType::Retail => write!(f, "Retail"),
+            ConsoleType::Testkit => write!(f, "Test kit"),
+            ConsoleType::Unknown(x) => write!(f, "Unknown console type ({})", x),
+        }
+    }
+}
+
+impl From<&str> for ConsoleType {
+    fn from(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "devkit" | "dev kit" | "development kit" | "development" => Self::Devkit,
+            "retail" => Self::Retail,
+            "testkit" | "test kit" => Self::Testkit,
+            _ => Self::Unknown(String::from(value)),
+        }
+    }
+}
+
+// no media type value has been confirmed against a console yet, they are all
+// kept as `Unknown` until named variants can be verified
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum DvdMediaType {
+    Unknown(u32),
+}
+
+impl fmt::Display for DvdMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DvdMediaType::Unknown(x) => write!(f, "Unknown media type ({})", x),
+        }
+    }
+}
+
+impl DvdMediaType {
+    pub fn from_u32(value: u32) -> Self {
+        Self::Unknown(value)
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    // standard FILE_ATTRIBUTE_* values reported by the console's file system
+    #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+    pub struct FileAttributes: u32 {
+        const READ_ONLY = 0x0000_0001;
+        const HIDDEN = 0x0000_0002;
+        const SYSTEM = 0x0000_0004;
+        const DIRECTORY = 0x0000_0010;
+        const ARCHIVE = 0x0000_0020;
+        const DEVICE = 0x0000_0040;
+        const NORMAL = 0x0000_0080;
+    }
+}
+
+// like `DvdMediaType`, the plugin's release type values are unconfirmed
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ReleaseType {
+    Unknown(u32),
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReleaseType::Unknown(x) => write!(f, "Unknown release type ({})", x),
+        }
+    }
+}
+
+impl ReleaseType {
+    pub fn from_u32(value: u32) -> Self {
+        Self::Unknown(value)
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+bitflags::bitflags! {
+    // creation flags passed to ExCreateThread. only the bits whose handling is
+    // public in Xenia's implementation are named, the rest (such as the
+    // processor affinity in the top byte) are retained unnamed.
+    #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+    pub struct ThreadFlags: u32 {
+        const SUSPENDED = 0x0000_0001;
+        const SYSTEM = 0x0000_0002;
+        const RETURN_KTHREAD = 0x0000_0080;
+    }
+}
+
+// values follow the kernel's KTHREAD_STATE
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ThreadRunState {
+    Initialized,
+    Ready,
+    Running,
+    Standby,
+    Terminated,
+    Waiting,
+    Transition,
+    Unknown(u32),
+}
+
+impl fmt::Display for ThreadRunState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThreadRunState::Initialized => write!(f, "Initialized"),
+            ThreadRunState::Ready => write!(f, "Ready"),
+            ThreadRunState::Running => write!(f, "Running"),
+            ThreadRunState::Standby => write!(f, "Standby"),
+            ThreadRunState::Terminated => write!(f, "Terminated"),
+            ThreadRunState::Waiting => write!(f, "Waiting"),
+            ThreadRunState::Transition => write!(f, "Transition"),
+            ThreadRunState::Unknown(x) => write!(f, "Unknown thread state ({})", x),
+        }
+    }
+}
+
+impl ThreadRunState {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Initialized,
+            1 => Self::Ready,
+            2 => Self::Running,
+            3 => Self::Standby,
+            4 => Self::Terminated,
+            5 => Self::Waiting,
+            6 => Self::Transition,
+            x => Self::Unknown(x),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Initialized => 0,
+            Self::Ready => 1,
+            Self::Running => 2,
+            Self::Standby => 3,
+            Self::Terminated => 4,
+            Self::Waiting => 5,
+            Self::Transition => 6,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+// the thread type values are unconfirmed, see `DvdMediaType`
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ThreadType {
+    Unknown(u32),
+}
+
+impl fmt::Display for ThreadType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThreadType::Unknown(x) => write!(f, "Unknown thread type ({})", x),
+        }
+    }
+}
+
+impl ThreadType {
+    pub fn from_u32(value: u32) -> Self {
+        Self::Unknown(value)
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TiltState {
+    Horizontal,
+    Vertical,
+    Unknown(u32),
+}
+
+impl fmt::Display for TiltState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TiltState::Horizontal => write!(f, "Horizontal"),
+            TiltState::Vertical => write!(f, "Vertical"),
+            TiltState::Unknown(x) => write!(f, "Unknown tilt state ({})", x),
+        }
+    }
+}
+
+impl TiltState {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Horizontal,
+            1 => Self::Vertical,
+            x => Self::Unknown(x),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Horizontal => 0,
+            Self::Vertical => 1,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TrayState {
+    Open,
+    Closed,
+    Opening,
+    Closing,
+    Unknown(u32),
+}
+
+impl fmt::Display for TrayState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrayState::Open => write!(f, "Tray open"),
+            TrayState::Closed => write!(f, "Tray closed"),
+            TrayState::Opening => write!(f, "Tray opening"),
+            TrayState::Closing => write!(f, "Tray closing"),
+            TrayState::Unknown(x) => write!(f, "Unknown tray state ({})", x),
+        }
+    }
+}
+
+impl TrayState {
+    // takes the bare state number, see `Smc::tray_state` for the SMC event form
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0 => Self::Open,
+            2 => Self::Closed,
+            3 => Self::Opening,
+            4 => Self::Closing,
+            x => Self::Unknown(x),
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            Self::Open => 0,
+            Self::Closed => 2,
+            Self::Opening => 3,
+            Self::Closing => 4,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
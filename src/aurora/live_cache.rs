@@ -0,0 +1,227 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use crate::aurora::http::HttpClient;
+use crate::utils::GenericResult;
+use crate::xboxcatalog;
+use log::error;
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+const LIVE_NAMESPACE: &str = "http://marketplace.xboxlive.com/resource/product/v1";
+
+// the live cache payload is taken to be the Xbox catalog FindGames response
+// that Aurora fetches for a title. this has not been confirmed against a
+// payload captured from a console, so only the title name, description and
+// images are modelled. `serialize` writes them back in the same shape.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiveCache {
+    pub title: String,
+    pub description: String,
+    pub images: Vec<LiveCacheImage>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiveCacheImage {
+    pub media_type: usize,
+    pub relationship_type: Option<usize>,
+    pub format: usize,
+    pub size: usize,
+    pub file_url: String,
+}
+
+impl From<&xboxcatalog::LiveImage> for LiveCacheImage {
+    fn from(live_image: &xboxcatalog::LiveImage) -> Self {
+        Self {
+            media_type: live_image.media_type,
+            relationship_type: live_image.relationship_type,
+            format: live_image.format,
+            size: live_image.size,
+            file_url: live_image.file_url.clone(),
+        }
+    }
+}
+
+impl LiveCache {
+    pub fn parse(payload: &str) -> GenericResult<Self> {
+        let has_entry = xml::reader::EventReader::from_str(payload)
+            .into_iter()
+            .any(|x| {
+                matches!(x, Ok(xml::reader::XmlEvent::StartElement { name, .. })
+                    if name.local_name == "entry")
+            });
+        let details = xboxcatalog::game_details_from_xml(payload);
+        let live_images = xboxcatalog::live_images_from_xml(payload);
+        match (has_entry, details, live_images) {
+            (true, Ok(details), Ok(live_images)) => Ok(Self::from_catalog(&details, &live_images)),
+            (false, Ok(_), Ok(_)) => {
+                let msg =
+                    "Failed to parse LiveCache from payload. Payload does not contain an entry.";
+                error!("{}", msg);
+                Err(msg.into())
+            }
+            _ => {
+                let msg = "Failed to parse LiveCache from payload.";
+                error!("{}", msg);
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub fn serialize(&self) -> GenericResult<String> {
+        let mut buffer = Vec::new();
+        let mut writer = xml::writer::EmitterConfig::new()
+            .perform_indent(true)
+            .create_writer(&mut buffer);
+        let events = [
+            xml::writer::XmlEvent::start_element("feed")
+                .default_ns(ATOM_NAMESPACE)
+                .ns("live", LIVE_NAMESPACE)
+                .into(),
+            xml::writer::XmlEvent::start_element("entry").into(),
+        ];
+        for event in events {
+            writer.write::<xml::writer::XmlEvent>(event)?;
+        }
+        write_text_element(&mut writer, "live:fullTitle", &self.title)?;
+        write_text_element(&mut writer, "live:fullDescription", &self.description)?;
+        writer.write(xml::writer::XmlEvent::start_element("live:images"))?;
+        for image in self.images.iter() {
+            writer.write(xml::writer::XmlEvent::start_element("live:image"))?;
+            write_text_element(
+                &mut writer,
+                "live:imageMediaType",
+                &image.media_type.to_string(),
+            )?;
+            if let Some(x) = image.relationship_type {
+                write_text_element(&mut writer, "live:relationshipType", &x.to_string())?;
+            }
+            write_text_element(&mut writer, "live:format", &image.format.to_string())?;
+            write_text_element(&mut writer, "live:size", &image.size.to_string())?;
+            write_text_element(&mut writer, "live:fileUrl", &image.file_url)?;
+            writer.write(xml::writer::XmlEvent::end_element())?;
+        }
+        // images, entry and feed
+        for _ in 0..3 {
+            writer.write(xml::writer::XmlEvent::end_element())?;
+        }
+        Ok(String::from_utf8(buffer)?)
+    }
+
+    // the full title and description are preferred over the reduced ones
+    pub fn from_catalog(
+        details: &xboxcatalog::GameDetails,
+        live_images: &[xboxcatalog::LiveImage],
+    ) -> Self {
+        let first = |values: [&Option<String>; 2]| {
+            values
+                .into_iter()
+                .flatten()
+                .next()
+                .cloned()
+                .unwrap_or_default()
+        };
+        Self {
+            title: first([&details.full_title, &details.reduced_title]),
+            description: first([&details.full_description, &details.reduced_description]),
+            images: live_images.iter().map(LiveCacheImage::from).collect(),
+        }
+    }
+
+    // queries the xbox catalog and builds the live cache from its response
+    pub async fn fetch_from_catalog(
+        title_id: usize,
+        locale: xboxcatalog::Locale,
+    ) -> GenericResult<Self> {
+        let xml =
+            xboxcatalog::find_games_xml(&xboxcatalog::media_id_for_title_id(title_id), locale)
+                .await?;
+        Self::parse(&xml)
+    }
+
+    pub async fn fetch(client: &HttpClient, token: Option<&str>) -> GenericResult<Self> {
+        Self::parse(&client.get_title_live_cache(token).await?)
+    }
+
+    pub async fn push(&self, client: &HttpClient, token: Option<&str>) -> GenericResult<()> {
+        client
+            .post_title_live_cache(token, &self.serialize()?)
+            .await
+    }
+}
+
+fn write_text_element<W: std::io::Write>(
+    writer: &mut xml::writer::EventWriter<W>,
+    name: &str,
+    value: &str,
+) -> GenericResult<()> {
+    writer.write(xml::writer::XmlEvent::start_element(name))?;
+    writer.write(xml::writer::XmlEvent::characters(value))?;
+    writer.write(xml::writer::XmlEvent::end_element())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/title_live_cache.xml");
+
+    #[test]
+    fn parse_fixture() {
+        let live_cache = LiveCache::parse(FIXTURE).unwrap();
+        assert_eq!(live_cache.title, "Sample Title: Extended Edition");
+        assert_eq!(
+            live_cache.description,
+            "A longer sample description & more."
+        );
+        assert_eq!(live_cache.images.len(), 2);
+        assert_eq!(live_cache.images[0].relationship_type, Some(15));
+        assert!(live_cache.images[1].file_url.ends_with("/banner.png"));
+    }
+
+    #[test]
+    fn round_trip_fixture() {
+        let live_cache = LiveCache::parse(FIXTURE).unwrap();
+        let payload = live_cache.serialize().unwrap();
+        assert_eq!(LiveCache::parse(&payload).unwrap(), live_cache);
+    }
+
+    #[test]
+    fn round_trip_edited_fields() {
+        let mut live_cache = LiveCache::parse(FIXTURE).unwrap();
+        live_cache.title = String::from("Edited <Title>");
+        live_cache.description = String::new();
+        live_cache.images.truncate(1);
+        live_cache.images[0].relationship_type = None;
+        let payload = live_cache.serialize().unwrap();
+        assert_eq!(LiveCache::parse(&payload).unwrap(), live_cache);
+    }
+
+    #[test]
+    fn reject_other_payloads() {
+        for payload in [
+            "",
+            "{\"titleid\":\"4D5307E6\"}",
+            "<feed></feed>",
+            "<feed><entry>",
+        ] {
+            assert!(LiveCache::parse(payload).is_err(), "accepted '{}'", payload);
+        }
+    }
+}
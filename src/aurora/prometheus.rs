@@ -0,0 +1,407 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::fmt::Write as FmtWrite;
+use std::sync::Arc;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas::{self, celsius};
+use crate::utils::GenericResult;
+use log::{debug, error, warn};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+const MAX_REQUEST_HEAD_BYTES: usize = 8192;
+
+#[derive(Clone, Debug, Default)]
+pub struct PrometheusExporter {
+    consoles: Vec<(String, HttpClient)>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ConsoleMetrics {
+    up: bool,
+    temperature: Option<http_schemas::Temperature>,
+    memory: Option<http_schemas::Memory>,
+    bandwidth: Option<http_schemas::SystemlinkBandwidth>,
+    title_id: Option<u32>,
+    signed_in_profiles: Option<usize>,
+}
+
+impl PrometheusExporter {
+    pub fn new() -> Self {
+        Self {
+            consoles: Vec::new(),
+        }
+    }
+
+    pub fn add_console(mut self, label: &str, client: HttpClient) -> Self {
+        self.consoles.push((String::from(label), client));
+        self
+    }
+
+    pub async fn render(&self) -> String {
+        let futures = self
+            .consoles
+            .iter()
+            .map(|(label, client)| async move { (label, gather(label, client).await) });
+        let gathered = futures::future::join_all(futures).await;
+        let mut output = String::new();
+        write_family(
+            &mut output,
+            "aurora_up",
+            "Whether the console responded to the last scrape.",
+            "gauge",
+            gathered
+                .iter()
+                .map(|(label, m)| (labels(label, None), Some(m.up as u32 as f64))),
+        );
+        write_family(
+            &mut output,
+            "aurora_temperature_celsius",
+            "Current console temperature readings in degrees celsius.",
+            "gauge",
+            gathered.iter().flat_map(|(label, m)| {
+                let t = m.temperature.as_ref();
+                [
+                    ("cpu", t.map(|x| celsius(x.cpu, x.celsius))),
+                    ("gpu", t.map(|x| celsius(x.gpu, x.celsius))),
+                    ("memory", t.map(|x| celsius(x.memory, x.celsius))),
+                    ("case", t.map(|x| celsius(x.case, x.celsius))),
+                ]
+                .into_iter()
+                .map(|(sensor, value)| (labels(label, Some(("sensor", sensor))), value))
+            }),
+        );
+        write_family(
+            &mut output,
+            "aurora_memory_bytes",
+            "Console memory usage in bytes.",
+            "gauge",
+            gathered.iter().flat_map(|(label, m)| {
+                let memory = m.memory.as_ref();
+                [
+                    ("free", memory.map(|x| x.free as f64)),
+                    ("used", memory.map(|x| x.used as f64)),
+                    ("total", memory.map(|x| x.total as f64)),
+                ]
+                .into_iter()
+                .map(|(kind, value)| (labels(label, Some(("type", kind))), value))
+            }),
+        );
+        write_family(
+            &mut output,
+            "aurora_systemlink_bytes_total",
+            "Bytes transferred over systemlink.",
+            "counter",
+            gathered.iter().flat_map(|(label, m)| {
+                let bandwidth = m.bandwidth.as_ref();
+                [
+                    ("upstream", bandwidth.map(|x| x.bytes.upstream as f64)),
+                    ("downstream", bandwidth.map(|x| x.bytes.downstream as f64)),
+                ]
+                .into_iter()
+                .map(|(direction, value)| (labels(label, Some(("direction", direction))), value))
+            }),
+        );
+        write_family(
+            &mut output,
+            "aurora_systemlink_rate",
+            "Current systemlink transfer rate as reported by the console.",
+            "gauge",
+            gathered.iter().flat_map(|(label, m)| {
+                let bandwidth = m.bandwidth.as_ref();
+                [
+                    ("upstream", bandwidth.map(|x| x.rate.upstream as f64)),
+                    ("downstream", bandwidth.map(|x| x.rate.downstream as f64)),
+                ]
+                .into_iter()
+                .map(|(direction, value)| (labels(label, Some(("direction", direction))), value))
+            }),
+        );
+        write_family(
+            &mut output,
+            "aurora_title_id",
+            "Title ID of the title that is currently running.",
+            "gauge",
+            gathered
+                .iter()
+                .map(|(label, m)| (labels(label, None), m.title_id.map(|x| x as f64))),
+        );
+        write_family(
+            &mut output,
+            "aurora_profiles_signed_in",
+            "Number of profiles that are signed in.",
+            "gauge",
+            gathered
+                .iter()
+                .map(|(label, m)| (labels(label, None), m.signed_in_profiles.map(|x| x as f64))),
+        );
+        output
+    }
+
+    // serves `GET /metrics` on `listener` until an accept error occurs
+    pub async fn serve(self, listener: TcpListener) -> GenericResult<()> {
+        let exporter = Arc::new(self);
+        loop {
+            let (stream, address) = listener.accept().await?;
+            debug!(
+                "Prometheus Exporter: Accepted connection from '{}'.",
+                address
+            );
+            let exporter = exporter.clone();
+            tokio::spawn(async move {
+                if let Err(err) = exporter.handle_connection(stream).await {
+                    warn!(
+                        "Prometheus Exporter: Failed to handle request from '{}'. Got the following error: {}",
+                        address, err
+                    );
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(&self, mut stream: TcpStream) -> GenericResult<()> {
+        let mut head: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.windows(4).any(|x| x == b"\r\n\r\n") {
+            let bytes_read = stream.read(&mut buf).await?;
+            if bytes_read == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..bytes_read]);
+            if head.len() > MAX_REQUEST_HEAD_BYTES {
+                return write_response(&mut stream, "431 Request Header Fields Too Large", "")
+                    .await;
+            }
+        }
+        let request = String::from_utf8_lossy(&head);
+        let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+        let method = request_line.next().unwrap_or("");
+        let path = request_line.next().unwrap_or("");
+        match (method, path.split('?').next().unwrap_or("")) {
+            ("GET", "/metrics") => {
+                write_response(&mut stream, "200 OK", &self.render().await).await
+            }
+            ("GET", _) => write_response(&mut stream, "404 Not Found", "").await,
+            _ => write_response(&mut stream, "405 Method Not Allowed", "").await,
+        }
+    }
+}
+
+async fn gather(label: &str, client: &HttpClient) -> ConsoleMetrics {
+    let mut metrics = ConsoleMetrics::default();
+    let token = match client.new_token().await {
+        Ok(x) => x,
+        Err(err) => {
+            error!(
+                "Prometheus Exporter: Failed to authenticate with console '{}'. Got the following error: {}",
+                label, err
+            );
+            return metrics;
+        }
+    };
+    let token = token.as_deref();
+    let (temperature, memory, bandwidth, title, profiles) = futures::join!(
+        client.get_temperature(token),
+        client.get_memory(token),
+        client.get_systemlink_bandwidth(token),
+        client.get_title(token),
+        client.get_profile(token),
+    );
+    metrics.up = temperature.is_ok()
+        || memory.is_ok()
+        || bandwidth.is_ok()
+        || title.is_ok()
+        || profiles.is_ok();
+    if !metrics.up {
+        warn!(
+            "Prometheus Exporter: Console '{}' did not respond to any request.",
+            label
+        );
+    }
+    metrics.temperature = temperature.ok();
+    metrics.memory = memory.ok();
+    metrics.bandwidth = bandwidth.ok();
+    metrics.title_id = title
+        .ok()
+        .and_then(|x| http_schemas::parse_hex_u32(&x.titleid).ok());
+    metrics.signed_in_profiles = profiles
+        .ok()
+        .map(|x| x.iter().filter(|p| p.signedin != 0).count());
+    metrics
+}
+
+fn labels(console: &str, extra: Option<(&str, &str)>) -> String {
+    match extra {
+        Some((name, value)) => format!(
+            "console=\"{}\",{}=\"{}\"",
+            escape_label_value(console),
+            name,
+            escape_label_value(value)
+        ),
+        None => format!("console=\"{}\"", escape_label_value(console)),
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_family<I>(output: &mut String, name: &str, help: &str, type_: &str, samples: I)
+where
+    I: Iterator<Item = (String, Option<f64>)>,
+{
+    let _ = writeln!(output, "# HELP {} {}", name, help);
+    let _ = writeln!(output, "# TYPE {} {}", name, type_);
+    for (labels, value) in samples {
+        if let Some(x) = value {
+            let _ = writeln!(output, "{}{{{}}} {}", name, labels, x);
+        }
+    }
+}
+
+async fn write_response(stream: &mut TcpStream, status: &str, body: &str) -> GenericResult<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{spawn_http_stub, StubRoute};
+
+    #[tokio::test]
+    async fn scrape_metrics_from_stub_console() {
+        let console = spawn_http_stub(
+            vec![
+                StubRoute::new(
+                    "POST",
+                    "/authenticate",
+                    200,
+                    r#"{"token":"stub-token"}"#,
+                ),
+                StubRoute::new(
+                    "GET",
+                    "/temperature",
+                    200,
+                    r#"{"case":40.0,"celsius":true,"cpu":55.5,"gpu":61.0,"memory":50.0}"#,
+                ),
+                StubRoute::new(
+                    "GET",
+                    "/memory",
+                    200,
+                    r#"{"free":100,"total":300,"used":200}"#,
+                ),
+                StubRoute::new(
+                    "GET",
+                    "/systemlink/bandwidth",
+                    200,
+                    r#"{"bytes":{"downstream":10,"upstream":20},"rate":{"downstream":1.5,"upstream":2.5}}"#,
+                ),
+                StubRoute::new(
+                    "GET",
+                    "/title",
+                    200,
+                    r#"{"disc":{"count":1,"current":1},"mediaid":"0","path":"x","resolution":{"height":720,"width":1280},"titleid":"0x4D5307E6","tuver":0,"version":{"base":"0","current":"0"}}"#,
+                ),
+                StubRoute::new(
+                    "GET",
+                    "/profile",
+                    200,
+                    r#"[{"gamerscore":1,"gamertag":"a","index":0,"signedin":1,"xuid":"1"},{"gamerscore":0,"gamertag":"","index":1,"signedin":0,"xuid":"0"}]"#,
+                ),
+            ],
+            Some("stub-token"),
+        )
+        .await;
+        let exporter = PrometheusExporter::new()
+            .add_console(
+                "living \"room\"",
+                HttpClient::new(
+                    console.ip().to_string(),
+                    console.port() as usize,
+                    Some(String::from("user")),
+                    Some(String::from("pass")),
+                ),
+            )
+            .add_console(
+                "offline",
+                HttpClient::new(String::from("127.0.0.1"), 1, None, None),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(exporter.serve(listener));
+
+        let resp = reqwest::get(format!("http://{}/metrics", address))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::OK);
+        assert!(resp.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = resp.text().await.unwrap();
+        let console = r#"console="living \"room\"""#;
+        for line in [
+            "# TYPE aurora_up gauge".to_string(),
+            format!("aurora_up{{{}}} 1", console),
+            r#"aurora_up{console="offline"} 0"#.to_string(),
+            format!(
+                "aurora_temperature_celsius{{{},sensor=\"cpu\"}} 55.5",
+                console
+            ),
+            format!("aurora_memory_bytes{{{},type=\"used\"}} 200", console),
+            "# TYPE aurora_systemlink_bytes_total counter".to_string(),
+            format!(
+                "aurora_systemlink_bytes_total{{{},direction=\"upstream\"}} 20",
+                console
+            ),
+            format!(
+                "aurora_systemlink_rate{{{},direction=\"downstream\"}} 1.5",
+                console
+            ),
+            format!("aurora_title_id{{{}}} 1297287142", console),
+            format!("aurora_profiles_signed_in{{{}}} 1", console),
+        ] {
+            assert!(
+                body.lines().any(|x| x == line),
+                "missing '{}' in:\n{}",
+                line,
+                body
+            );
+        }
+        assert!(!body.contains(r#"aurora_memory_bytes{console="offline""#));
+
+        let resp = reqwest::get(format!("http://{}/other", address))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+    }
+}
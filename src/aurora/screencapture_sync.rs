@@ -0,0 +1,334 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas;
+use crate::utils::GenericResult;
+use log::{debug, error, warn};
+
+const STATE_FILE_NAME: &str = ".screencapture-sync.json";
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ScreencaptureSyncState {
+    // maps the console's screencapture file name to the local file name
+    pub synced: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ScreencaptureSyncReport {
+    pub downloaded: Vec<PathBuf>,
+    pub skipped: usize,
+    pub deleted: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ScreencaptureSync {
+    client: HttpClient,
+    library_dir: PathBuf,
+    delete_after_download: bool,
+}
+
+impl ScreencaptureSync {
+    pub fn new(client: HttpClient, library_dir: &Path) -> Self {
+        Self {
+            client,
+            library_dir: library_dir.to_path_buf(),
+            delete_after_download: false,
+        }
+    }
+
+    pub fn delete_after_download(mut self, delete_after_download: bool) -> Self {
+        self.delete_after_download = delete_after_download;
+        self
+    }
+
+    pub fn library_dir(&self) -> &Path {
+        &self.library_dir
+    }
+
+    pub fn state_file(&self) -> PathBuf {
+        self.library_dir.join(STATE_FILE_NAME)
+    }
+
+    pub fn load_state(&self) -> GenericResult<ScreencaptureSyncState> {
+        let state_file = self.state_file();
+        if !state_file.is_file() {
+            return Ok(ScreencaptureSyncState::default());
+        }
+        match serde_json::from_slice(&std::fs::read(&state_file)?) {
+            Ok(x) => Ok(x),
+            Err(err) => {
+                let msg = format!(
+                    "Failed to parse screencapture sync state file '{}'.",
+                    state_file.display()
+                );
+                error!("{} Got the following error: {}", msg, err);
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub fn save_state(&self, state: &ScreencaptureSyncState) -> GenericResult<()> {
+        // write to a temporary file first so an interrupted write cannot
+        // corrupt the existing state file
+        let state_file = self.state_file();
+        let temp_file = state_file.with_extension("json.part");
+        std::fs::write(&temp_file, serde_json::to_vec_pretty(state)?)?;
+        std::fs::rename(&temp_file, &state_file)?;
+        Ok(())
+    }
+
+    pub async fn sync(&self, token: Option<&str>) -> GenericResult<ScreencaptureSyncReport> {
+        std::fs::create_dir_all(&self.library_dir)?;
+        let mut state = self.load_state()?;
+        let mut report = ScreencaptureSyncReport::default();
+        for meta in self.client.get_screencapture_meta_list(token).await? {
+            if let Some(local_name) = state.synced.get(&meta.filename) {
+                debug!(
+                    "Screencapture Sync: '{}' was already downloaded to '{}'.",
+                    meta.filename, local_name
+                );
+                report.skipped += 1;
+                // captures from earlier runs are deleted once deletion is
+                // enabled, as long as the local copy is still intact
+                let local_path = self.library_dir.join(local_name);
+                if self.delete_after_download && is_complete(&local_path, &meta) {
+                    self.delete(token, &meta, &mut report).await;
+                }
+                continue;
+            }
+            match self.download(token, &meta).await {
+                Ok(local_path) => {
+                    let local_name = match local_path.file_name().and_then(|x| x.to_str()) {
+                        Some(x) => String::from(x),
+                        None => {
+                            let msg = format!(
+                                "Failed to get file name for path '{}'.",
+                                local_path.display()
+                            );
+                            report.failed.push((meta.filename.clone(), msg));
+                            continue;
+                        }
+                    };
+                    state.synced.insert(meta.filename.clone(), local_name);
+                    self.save_state(&state)?;
+                    if self.delete_after_download && is_complete(&local_path, &meta) {
+                        self.delete(token, &meta, &mut report).await;
+                    }
+                    report.downloaded.push(local_path);
+                }
+                Err(err) => {
+                    warn!(
+                        "Screencapture Sync: Failed to download '{}'. Got the following error: {}",
+                        meta.filename, err
+                    );
+                    report.failed.push((meta.filename.clone(), err.to_string()));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    async fn delete(
+        &self,
+        token: Option<&str>,
+        meta: &http_schemas::ScreencaptureMeta,
+        report: &mut ScreencaptureSyncReport,
+    ) {
+        match self
+            .client
+            .delete_screencapture(token, &meta.filename)
+            .await
+        {
+            Ok(_) => report.deleted.push(meta.filename.clone()),
+            Err(err) => report.failed.push((meta.filename.clone(), err.to_string())),
+        }
+    }
+
+    async fn download(
+        &self,
+        token: Option<&str>,
+        meta: &http_schemas::ScreencaptureMeta,
+    ) -> GenericResult<PathBuf> {
+        debug!("Screencapture Sync: Downloading '{}'.", meta.filename);
+        let data = self
+            .client
+            .get_image_screencapture(token, &meta.filename)
+            .await?;
+        if meta.filesize != 0 && data.len() != meta.filesize as usize {
+            return Err(format!(
+                "Expected {} bytes for screencapture '{}' but got {}.",
+                meta.filesize,
+                meta.filename,
+                data.len()
+            )
+            .into());
+        }
+        let local_path = self.unused_path(&local_file_stem(meta), &local_file_extension(meta));
+        let temp_path = local_path.with_extension("part");
+        std::fs::write(&temp_path, &data)?;
+        std::fs::rename(&temp_path, &local_path)?;
+        // verify what actually landed on disk before anything is deleted
+        let written = std::fs::metadata(&local_path)?.len();
+        if written != data.len() as u64 {
+            std::fs::remove_file(&local_path)?;
+            return Err(format!(
+                "Expected to write {} bytes to '{}' but wrote {}.",
+                data.len(),
+                local_path.display(),
+                written
+            )
+            .into());
+        }
+        std::fs::write(
+            local_path.with_extension("json"),
+            serde_json::to_vec_pretty(meta)?,
+        )?;
+        Ok(local_path)
+    }
+
+    // the sidecar is named after the stem only, so captures that differ in
+    // format alone must not share it
+    fn unused_path(&self, stem: &str, extension: &str) -> PathBuf {
+        let mut path = self.library_dir.join(format!("{}.{}", stem, extension));
+        let mut index = 1;
+        while path.exists() || path.with_extension("json").exists() {
+            path = self
+                .library_dir
+                .join(format!("{}_{}.{}", stem, index, extension));
+            index += 1;
+        }
+        path
+    }
+}
+
+// a capture is only deleted from the console once the local copy has the
+// size the console reported. without a reported size it is always kept.
+fn is_complete(local_path: &Path, meta: &http_schemas::ScreencaptureMeta) -> bool {
+    if meta.filesize == 0 {
+        warn!(
+            "Screencapture Sync: Keeping '{}' on the console because its size is unknown.",
+            meta.filename
+        );
+        return false;
+    }
+    match std::fs::metadata(local_path) {
+        Ok(x) => x.is_file() && x.len() == meta.filesize as u64,
+        Err(_) => false,
+    }
+}
+
+fn local_file_stem(meta: &http_schemas::ScreencaptureMeta) -> String {
+    format!(
+        "{}_{}",
+        sanitize_file_name_part(&meta.titleid),
+        sanitize_file_name_part(&meta.timestamp)
+    )
+}
+
+fn local_file_extension(meta: &http_schemas::ScreencaptureMeta) -> String {
+    let format = sanitize_file_name_part(&meta.info.format).to_lowercase();
+    if !format.is_empty() {
+        return format;
+    }
+    match Path::new(&meta.filename)
+        .extension()
+        .and_then(|x| x.to_str())
+    {
+        Some(x) => sanitize_file_name_part(x).to_lowercase(),
+        None => String::from("bin"),
+    }
+}
+
+fn sanitize_file_name_part(value: &str) -> String {
+    value
+        .trim()
+        .chars()
+        .map(|c| match c.is_ascii_alphanumeric() || c == '-' {
+            true => c,
+            false => '-',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{spawn_recording_http_stub, StubRoute};
+
+    const META_LIST: &str = r#"[
+        {"filename": "a.png", "filesize": 4, "info": {"format": "png", "height": 720, "width": 1280}, "timestamp": "20260101120000", "titleid": "4D5307E6"},
+        {"filename": "b.jpg", "filesize": 0, "info": {"format": "jpg", "height": 720, "width": 1280}, "timestamp": "20260101120000", "titleid": "4D5307E6"}
+    ]"#;
+
+    #[tokio::test]
+    async fn sync_keeps_sidecars_apart_and_unverified_captures() {
+        let (address, requests) = spawn_recording_http_stub(
+            vec![
+                StubRoute::new("GET", "/screencapture/meta/list", 200, META_LIST),
+                StubRoute::new("GET", "/image/screencapture", 200, "abcd"),
+                StubRoute::new("DELETE", "/screencapture", 200, ""),
+            ],
+            None,
+        )
+        .await;
+        let library_dir = std::env::temp_dir().join(format!(
+            "libaustralis-screencapture-sync-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&library_dir);
+        let client = HttpClient::new(
+            address.ip().to_string(),
+            address.port() as usize,
+            None,
+            None,
+        );
+        let sync = ScreencaptureSync::new(client, &library_dir).delete_after_download(true);
+        let report = sync.sync(None).await.unwrap();
+        assert_eq!(report.downloaded.len(), 2);
+        for name in [
+            "4D5307E6_20260101120000.png",
+            "4D5307E6_20260101120000.json",
+            "4D5307E6_20260101120000_1.jpg",
+            "4D5307E6_20260101120000_1.json",
+        ] {
+            assert!(library_dir.join(name).is_file(), "{}", name);
+        }
+        let sidecar: http_schemas::ScreencaptureMeta = serde_json::from_slice(
+            &std::fs::read(library_dir.join("4D5307E6_20260101120000.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(sidecar.filename, "a.png");
+        // only the capture with a known size was verified and deleted
+        assert_eq!(report.deleted, vec![String::from("a.png")]);
+        let deletes = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|x| x.starts_with("DELETE"))
+            .count();
+        assert_eq!(deletes, 1);
+        std::fs::remove_dir_all(library_dir).unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas::{self, celsius};
+use crate::utils::GenericResult;
+use log::{debug, warn};
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TelemetrySample {
+    // milliseconds since the unix epoch
+    pub timestamp: u64,
+    pub temperature: Option<http_schemas::Temperature>,
+    pub smc: Option<http_schemas::Smc>,
+    pub memory: Option<http_schemas::Memory>,
+    pub bandwidth: Option<http_schemas::SystemlinkBandwidth>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Metric {
+    CpuTemperature,
+    GpuTemperature,
+    MemoryTemperature,
+    CaseTemperature,
+    MemoryFree,
+    MemoryUsed,
+    BandwidthUpstreamBytes,
+    BandwidthDownstreamBytes,
+    BandwidthUpstreamRate,
+    BandwidthDownstreamRate,
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Metric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Metric::CpuTemperature => "cpu_temperature",
+            Metric::GpuTemperature => "gpu_temperature",
+            Metric::MemoryTemperature => "memory_temperature",
+            Metric::CaseTemperature => "case_temperature",
+            Metric::MemoryFree => "memory_free",
+            Metric::MemoryUsed => "memory_used",
+            Metric::BandwidthUpstreamBytes => "bandwidth_upstream_bytes",
+            Metric::BandwidthDownstreamBytes => "bandwidth_downstream_bytes",
+            Metric::BandwidthUpstreamRate => "bandwidth_upstream_rate",
+            Metric::BandwidthDownstreamRate => "bandwidth_downstream_rate",
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = Metric> {
+        [
+            Metric::CpuTemperature,
+            Metric::GpuTemperature,
+            Metric::MemoryTemperature,
+            Metric::CaseTemperature,
+            Metric::MemoryFree,
+            Metric::MemoryUsed,
+            Metric::BandwidthUpstreamBytes,
+            Metric::BandwidthDownstreamBytes,
+            Metric::BandwidthUpstreamRate,
+            Metric::BandwidthDownstreamRate,
+        ]
+        .into_iter()
+    }
+
+    // temperatures are always returned in degrees celsius
+    pub fn value(&self, sample: &TelemetrySample) -> Option<f64> {
+        let temperature = sample.temperature.as_ref();
+        match self {
+            Metric::CpuTemperature => temperature.map(|x| celsius(x.cpu, x.celsius)),
+            Metric::GpuTemperature => temperature.map(|x| celsius(x.gpu, x.celsius)),
+            Metric::MemoryTemperature => temperature.map(|x| celsius(x.memory, x.celsius)),
+            Metric::CaseTemperature => temperature.map(|x| celsius(x.case, x.celsius)),
+            Metric::MemoryFree => sample.memory.as_ref().map(|x| x.free as f64),
+            Metric::MemoryUsed => sample.memory.as_ref().map(|x| x.used as f64),
+            Metric::BandwidthUpstreamBytes => {
+                sample.bandwidth.as_ref().map(|x| x.bytes.upstream as f64)
+            }
+            Metric::BandwidthDownstreamBytes => {
+                sample.bandwidth.as_ref().map(|x| x.bytes.downstream as f64)
+            }
+            Metric::BandwidthUpstreamRate => {
+                sample.bandwidth.as_ref().map(|x| x.rate.upstream as f64)
+            }
+            Metric::BandwidthDownstreamRate => {
+                sample.bandwidth.as_ref().map(|x| x.rate.downstream as f64)
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MetricStats {
+    pub min: f64,
+    pub max: f64,
+    pub average: f64,
+    pub rolling_average: f64,
+    pub count: usize,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ThresholdDirection {
+    Above,
+    Below,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Threshold {
+    pub metric: Metric,
+    pub direction: ThresholdDirection,
+    pub value: f64,
+}
+
+impl Threshold {
+    pub fn above(metric: Metric, value: f64) -> Self {
+        Self {
+            metric,
+            direction: ThresholdDirection::Above,
+            value,
+        }
+    }
+
+    pub fn below(metric: Metric, value: f64) -> Self {
+        Self {
+            metric,
+            direction: ThresholdDirection::Below,
+            value,
+        }
+    }
+
+    pub fn is_exceeded_by(&self, value: f64) -> bool {
+        match self.direction {
+            ThresholdDirection::Above => value > self.value,
+            ThresholdDirection::Below => value < self.value,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ThresholdState {
+    Exceeded,
+    Recovered,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ThresholdEvent {
+    pub threshold: Threshold,
+    pub state: ThresholdState,
+    pub value: f64,
+    pub timestamp: u64,
+}
+
+type ThresholdCallback = Box<dyn FnMut(&ThresholdEvent) + Send>;
+
+struct ThresholdWatch {
+    threshold: Threshold,
+    callback: ThresholdCallback,
+    exceeded: bool,
+}
+
+pub struct TelemetrySampler {
+    client: HttpClient,
+    interval: Duration,
+    capacity: usize,
+    rolling_window: usize,
+    history: VecDeque<TelemetrySample>,
+    thresholds: Vec<ThresholdWatch>,
+}
+
+impl TelemetrySampler {
+    pub fn new(client: HttpClient, interval: Duration, capacity: usize) -> Self {
+        let capacity = std::cmp::max(capacity, 1);
+        Self {
+            client,
+            interval,
+            capacity,
+            rolling_window: std::cmp::min(capacity, 10),
+            history: VecDeque::with_capacity(capacity),
+            thresholds: Vec::new(),
+        }
+    }
+
+    pub fn rolling_window(mut self, samples: usize) -> Self {
+        self.rolling_window = std::cmp::max(samples, 1);
+        self
+    }
+
+    // `callback` is called once when the threshold is crossed and once when
+    // the metric returns to the allowed range
+    pub fn add_threshold<F>(&mut self, threshold: Threshold, callback: F)
+    where
+        F: FnMut(&ThresholdEvent) + Send + 'static,
+    {
+        self.thresholds.push(ThresholdWatch {
+            threshold,
+            callback: Box::new(callback),
+            exceeded: false,
+        });
+    }
+
+    pub fn history(&self) -> &VecDeque<TelemetrySample> {
+        &self.history
+    }
+
+    pub fn latest(&self) -> Option<&TelemetrySample> {
+        self.history.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.history.clear();
+    }
+
+    pub async fn sample(&mut self, token: Option<&str>) -> GenericResult<&TelemetrySample> {
+        let temperature = self.client.get_temperature(token).await;
+        let smc = self.client.get_smc(token).await;
+        let memory = self.client.get_memory(token).await;
+        let bandwidth = self.client.get_systemlink_bandwidth(token).await;
+        if temperature.is_err() && smc.is_err() && memory.is_err() && bandwidth.is_err() {
+            return Err("Failed to get any telemetry values from the console.".into());
+        }
+        let sample = TelemetrySample {
+            timestamp: unix_timestamp_millis(),
+            temperature: ok_or_warn("temperature", temperature),
+            smc: ok_or_warn("smc", smc),
+            memory: ok_or_warn("memory", memory),
+            bandwidth: ok_or_warn("systemlink bandwidth", bandwidth),
+        };
+        self.push(sample);
+        match self.history.back() {
+            Some(x) => Ok(x),
+            None => Err("Telemetry history is empty.".into()),
+        }
+    }
+
+    // takes `count` samples at the sampler's fixed rate, or runs until the
+    // future is dropped if `count` is `None`. failed samples are logged and
+    // skipped so a rebooting console does not end the run.
+    pub async fn run(&mut self, token: Option<&str>, count: Option<usize>) {
+        let mut interval = tokio::time::interval(self.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut taken = 0;
+        while count.is_none_or(|x| taken < x) {
+            interval.tick().await;
+            if let Err(err) = self.sample(token).await {
+                warn!(
+                    "Telemetry: Failed to take sample. Got the following error: {}",
+                    err
+                );
+            }
+            taken += 1;
+        }
+    }
+
+    pub fn stats(&self, metric: Metric) -> Option<MetricStats> {
+        let values: Vec<f64> = self
+            .history
+            .iter()
+            .filter_map(|x| metric.value(x))
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        let rolling_start = values.len().saturating_sub(self.rolling_window);
+        let rolling = &values[rolling_start..];
+        Some(MetricStats {
+            min: values.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            average: values.iter().sum::<f64>() / values.len() as f64,
+            rolling_average: rolling.iter().sum::<f64>() / rolling.len() as f64,
+            count: values.len(),
+        })
+    }
+
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> GenericResult<()> {
+        let mut columns = vec!["timestamp"];
+        columns.extend(Metric::all().map(|x| x.as_str()));
+        columns.extend([
+            "celsius",
+            "memory_total",
+            "smc_cpu_target",
+            "smc_gpu_target",
+            "smc_memory_target",
+        ]);
+        writeln!(writer, "{}", columns.join(","))?;
+        for sample in self.history.iter() {
+            let mut row = vec![sample.timestamp.to_string()];
+            row.extend(Metric::all().map(|x| csv_value(x.value(sample))));
+            row.push(csv_value(sample.temperature.as_ref().map(|x| x.celsius)));
+            row.push(csv_value(sample.memory.as_ref().map(|x| x.total)));
+            // the targets are converted like the metrics, `celsius` keeps the
+            // unit the console reported in
+            let smc = sample.smc.as_ref().map(|x| &x.temperature);
+            row.push(csv_value(smc.map(|x| celsius(x.target.cpu, x.celsius))));
+            row.push(csv_value(smc.map(|x| celsius(x.target.gpu, x.celsius))));
+            row.push(csv_value(smc.map(|x| celsius(x.target.memory, x.celsius))));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+
+    pub fn export_json_lines<W: Write>(&self, writer: &mut W) -> GenericResult<()> {
+        for sample in self.history.iter() {
+            serde_json::to_writer(&mut *writer, sample)?;
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, sample: TelemetrySample) {
+        for watch in self.thresholds.iter_mut() {
+            let value = match watch.threshold.metric.value(&sample) {
+                Some(x) => x,
+                None => continue,
+            };
+            let exceeded = watch.threshold.is_exceeded_by(value);
+            if exceeded == watch.exceeded {
+                continue;
+            }
+            watch.exceeded = exceeded;
+            let state = match exceeded {
+                true => ThresholdState::Exceeded,
+                false => ThresholdState::Recovered,
+            };
+            debug!(
+                "Telemetry: Threshold {:?} {:?} with value {}.",
+                watch.threshold, state, value
+            );
+            (watch.callback)(&ThresholdEvent {
+                threshold: watch.threshold,
+                state,
+                value,
+                timestamp: sample.timestamp,
+            });
+        }
+        while self.history.len() >= self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(sample);
+    }
+}
+
+fn csv_value<T: ToString>(value: Option<T>) -> String {
+    match value {
+        Some(x) => x.to_string(),
+        None => String::new(),
+    }
+}
+
+fn ok_or_warn<T>(name: &str, result: GenericResult<T>) -> Option<T> {
+    match result {
+        Ok(x) => Some(x),
+        Err(err) => {
+            warn!(
+                "Telemetry: Failed to get {}. Got the following error: {}",
+                name, err
+            );
+            None
+        }
+    }
+}
+
+fn unix_timestamp_millis() -> u64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(x) => x.as_millis() as u64,
+        Err(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temperatures_are_converted_to_celsius() {
+        let sample = |temperature: &str| TelemetrySample {
+            timestamp: 0,
+            temperature: Some(serde_json::from_str(temperature).unwrap()),
+            smc: None,
+            memory: None,
+            bandwidth: None,
+        };
+        let fahrenheit =
+            sample(r#"{"case":104.0,"celsius":false,"cpu":131.0,"gpu":140.0,"memory":122.0}"#);
+        assert_eq!(Metric::CaseTemperature.value(&fahrenheit), Some(40.0));
+        assert_eq!(Metric::CpuTemperature.value(&fahrenheit), Some(55.0));
+        assert_eq!(Metric::GpuTemperature.value(&fahrenheit), Some(60.0));
+        assert_eq!(Metric::MemoryTemperature.value(&fahrenheit), Some(50.0));
+        let celsius = sample(r#"{"case":40.0,"celsius":true,"cpu":55.5,"gpu":61.0,"memory":50.0}"#);
+        assert_eq!(Metric::CpuTemperature.value(&celsius), Some(55.5));
+    }
+}
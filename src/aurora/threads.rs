@@ -0,0 +1,283 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::future::Future;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas::{self, ThreadFlags, ThreadRunState, ThreadType};
+use crate::utils::{block_on, GenericResult};
+use log::{debug, error};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadInfo {
+    pub address: u32,
+    pub id: u32,
+    pub flags: ThreadFlags,
+    pub priority: u32,
+    pub state: ThreadRunState,
+    pub thread_type: ThreadType,
+}
+
+impl TryFrom<&http_schemas::Thread> for ThreadInfo {
+    type Error = crate::utils::GenericError;
+
+    fn try_from(thread: &http_schemas::Thread) -> GenericResult<Self> {
+        Ok(Self {
+            address: http_schemas::parse_hex_u32(&thread.address)?,
+            id: http_schemas::parse_hex_u32(&thread.id)?,
+            flags: thread.thread_flags()?,
+            priority: thread.priority,
+            state: thread.run_state(),
+            thread_type: thread.thread_type(),
+        })
+    }
+}
+
+pub async fn get_threads(
+    client: &HttpClient,
+    token: Option<&str>,
+) -> GenericResult<Vec<ThreadInfo>> {
+    client
+        .get_thread(token)
+        .await?
+        .iter()
+        .map(ThreadInfo::try_from)
+        .collect()
+}
+
+// highest priority first, ties are ordered by thread id
+pub fn sort_by_priority(threads: &mut [ThreadInfo]) {
+    threads.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+}
+
+pub fn filter_by_type(threads: &[ThreadInfo], thread_type: ThreadType) -> Vec<ThreadInfo> {
+    threads
+        .iter()
+        .filter(|x| x.thread_type == thread_type)
+        .cloned()
+        .collect()
+}
+
+pub fn filter_by_priority(threads: &[ThreadInfo], min: u32, max: u32) -> Vec<ThreadInfo> {
+    threads
+        .iter()
+        .filter(|x| x.priority >= min && x.priority <= max)
+        .cloned()
+        .collect()
+}
+
+pub async fn is_suspended(client: &HttpClient, token: Option<&str>) -> GenericResult<bool> {
+    Ok(client.get_thread_state(token).await?.state != 0)
+}
+
+// how long dropping a `SuspendGuard` waits for the console to resume
+const DROP_RESUME_TIMEOUT: Duration = Duration::from_secs(10);
+
+// suspends the title's threads until `resume` is called. if the guard is
+// dropped without calling `resume`, the resume request is sent from a
+// dedicated thread and the drop blocks until it completes or
+// `DROP_RESUME_TIMEOUT` passes. this works without a tokio runtime and while
+// one is shutting down, but blocks the dropping thread. if the console cannot
+// be reached in time the threads stay suspended and, since `drop` cannot
+// return errors, this is only logged. call `resume` to handle the error.
+#[derive(Debug)]
+pub struct SuspendGuard {
+    client: HttpClient,
+    token: Option<String>,
+    suspended: bool,
+}
+
+impl SuspendGuard {
+    pub async fn suspend(client: &HttpClient, token: Option<&str>) -> GenericResult<Self> {
+        debug!("Suspend Guard: Suspending title threads.");
+        client.post_thread_state(token, true).await?;
+        Ok(Self {
+            client: client.clone(),
+            token: token.map(String::from),
+            suspended: true,
+        })
+    }
+
+    pub async fn resume(mut self) -> GenericResult<()> {
+        debug!("Suspend Guard: Resuming title threads.");
+        self.suspended = false;
+        self.client
+            .post_thread_state(self.token.as_deref(), false)
+            .await
+    }
+}
+
+impl Drop for SuspendGuard {
+    fn drop(&mut self) {
+        if !self.suspended {
+            return;
+        }
+        debug!("Suspend Guard: Resuming title threads from drop.");
+        let client = self.client.clone();
+        let token = self.token.take();
+        let (sender, receiver) = mpsc::channel();
+        let spawned = std::thread::Builder::new()
+            .name(String::from("suspend-guard-resume"))
+            .spawn(move || {
+                let result = block_on(async {
+                    let request = client.post_thread_state(token.as_deref(), false);
+                    match tokio::time::timeout(DROP_RESUME_TIMEOUT, request).await {
+                        Ok(x) => x,
+                        Err(_) => Err("Timed out resuming title threads.".into()),
+                    }
+                });
+                let _ = sender.send(result.map_err(|x| x.to_string()));
+            });
+        if let Err(err) = spawned {
+            error!(
+                "Suspend Guard: Failed to start thread to resume title threads, they remain suspended. Got the following error: {}",
+                err
+            );
+            return;
+        }
+        match receiver.recv_timeout(DROP_RESUME_TIMEOUT + Duration::from_secs(1)) {
+            Ok(Ok(())) => debug!("Suspend Guard: Resumed title threads from drop."),
+            Ok(Err(err)) => error!(
+                "Suspend Guard: Failed to resume title threads, they remain suspended. Got the following error: {}",
+                err
+            ),
+            Err(_) => error!(
+                "Suspend Guard: Timed out resuming title threads, they may remain suspended."
+            ),
+        }
+    }
+}
+
+// runs `f` while the title's threads are suspended. the threads are resumed
+// before returning whether or not `f` succeeded.
+pub async fn with_threads_suspended<F, Fut, T>(
+    client: &HttpClient,
+    token: Option<&str>,
+    f: F,
+) -> GenericResult<T>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = GenericResult<T>>,
+{
+    let guard = SuspendGuard::suspend(client, token).await?;
+    let result = f().await;
+    let resume_result = guard.resume().await;
+    let value = result?;
+    resume_result?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{spawn_recording_http_stub, StubRoute};
+
+    fn stub_console(
+        runtime: &tokio::runtime::Runtime,
+    ) -> (HttpClient, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        let (address, requests) = runtime.block_on(spawn_recording_http_stub(
+            vec![StubRoute::new("POST", "/thread/state", 200, "")],
+            None,
+        ));
+        let client = HttpClient::new(
+            address.ip().to_string(),
+            address.port() as usize,
+            None,
+            None,
+        );
+        (client, requests)
+    }
+
+    #[test]
+    fn thread_info_keeps_unknown_flags_and_type() {
+        let threads: Vec<http_schemas::Thread> = serde_json::from_str(
+            r#"[
+                {"address": "0x8D3A0000", "flags": "0x04000081", "id": "0xF8000010", "priority": 10, "state": 2, "type": 7},
+                {"address": "0x8D3B0000", "flags": "0x00000002", "id": "0xF8000014", "priority": 12, "state": 5, "type": 1}
+            ]"#,
+        )
+        .unwrap();
+        let threads = threads
+            .iter()
+            .map(ThreadInfo::try_from)
+            .collect::<GenericResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(threads[0].id, 0xF800_0010);
+        assert!(threads[0]
+            .flags
+            .contains(ThreadFlags::SUSPENDED | ThreadFlags::RETURN_KTHREAD));
+        assert_eq!(threads[0].flags.bits(), 0x0400_0081);
+        assert_eq!(threads[0].thread_type.as_u32(), 7);
+        let filtered = filter_by_type(&threads, ThreadType::from_u32(1));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].flags, ThreadFlags::SYSTEM);
+    }
+
+    #[test]
+    fn dropped_guard_resumes_without_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (client, requests) = stub_console(&runtime);
+        let guard = runtime
+            .block_on(SuspendGuard::suspend(&client, None))
+            .unwrap();
+        drop(guard);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "POST /thread/state suspend=1",
+                "POST /thread/state suspend=0"
+            ]
+        );
+    }
+
+    #[test]
+    fn dropped_guard_resumes_on_early_return() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (client, requests) = stub_console(&runtime);
+        let result: GenericResult<()> = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(async {
+                let _guard = SuspendGuard::suspend(&client, None).await?;
+                Err("snapshot failed".into())
+            });
+        assert!(result.is_err());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "POST /thread/state suspend=1",
+                "POST /thread/state suspend=0"
+            ]
+        );
+    }
+
+    #[test]
+    fn resumed_guard_does_not_resume_again() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (client, requests) = stub_console(&runtime);
+        runtime.block_on(async {
+            let guard = SuspendGuard::suspend(&client, None).await.unwrap();
+            guard.resume().await.unwrap();
+        });
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+}
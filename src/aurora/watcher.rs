@@ -0,0 +1,198 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas;
+use crate::utils::GenericResult;
+use futures::stream::Stream;
+use log::{debug, warn};
+
+#[derive(Clone, Debug)]
+pub enum UpdateEvent {
+    TitleChanged(http_schemas::Title),
+    ProfileChanged(Vec<http_schemas::Profile>),
+    NewScreencapture(http_schemas::ScreencaptureMeta),
+    AchievementsChanged,
+    ConsoleUnreachable,
+    ConsoleReachable,
+}
+
+#[derive(Clone, Debug)]
+pub struct UpdateWatcher {
+    client: HttpClient,
+    interval: Duration,
+    max_backoff: Duration,
+}
+
+impl UpdateWatcher {
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            client,
+            interval: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    // the first successful poll only records the current counters, events are
+    // yielded for counters that change after that. while the console cannot be
+    // reached the poll delay doubles (up to `max_backoff`) and counters are
+    // compared against the last known values once it responds again.
+    pub fn into_stream(self) -> impl Stream<Item = UpdateEvent> {
+        let state = WatcherState {
+            watcher: self,
+            token: None,
+            last: None,
+            events: VecDeque::new(),
+            first_poll: true,
+            backoff: None,
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.events.pop_front() {
+                    return Some((event, state));
+                }
+                state.poll().await;
+            }
+        })
+    }
+}
+
+struct WatcherState {
+    watcher: UpdateWatcher,
+    token: Option<String>,
+    last: Option<http_schemas::UpdateNotification>,
+    events: VecDeque<UpdateEvent>,
+    first_poll: bool,
+    backoff: Option<Duration>,
+}
+
+impl WatcherState {
+    async fn poll(&mut self) {
+        if self.first_poll {
+            self.first_poll = false;
+        } else {
+            tokio::time::sleep(self.backoff.unwrap_or(self.watcher.interval)).await;
+        }
+        let notification = match self.update_notification().await {
+            Ok(x) => x,
+            Err(err) => {
+                self.mark_unreachable(err);
+                return;
+            }
+        };
+        if self.backoff.take().is_some() {
+            debug!("Update Watcher: Console is reachable again.");
+            self.events.push_back(UpdateEvent::ConsoleReachable);
+        }
+        let mut last = match self.last.take() {
+            Some(x) => x,
+            None => {
+                self.last = Some(notification);
+                return;
+            }
+        };
+        let client = &self.watcher.client;
+        let token = self.token.as_deref();
+        // a counter is only updated once its resource was fetched so that a
+        // failed fetch is retried on the next poll
+        if notification.title != last.title {
+            match client.get_title(token).await {
+                Ok(x) => {
+                    self.events.push_back(UpdateEvent::TitleChanged(x));
+                    last.title = notification.title;
+                }
+                Err(err) => warn!(
+                    "Update Watcher: Failed to get title. Got the following error: {}",
+                    err
+                ),
+            }
+        }
+        if notification.profiles != last.profiles {
+            match client.get_profile(token).await {
+                Ok(x) => {
+                    self.events.push_back(UpdateEvent::ProfileChanged(x));
+                    last.profiles = notification.profiles;
+                }
+                Err(err) => warn!(
+                    "Update Watcher: Failed to get profiles. Got the following error: {}",
+                    err
+                ),
+            }
+        }
+        if notification.screencapture != last.screencapture {
+            match client.get_screencapture_meta(token).await {
+                Ok(x) => {
+                    self.events.push_back(UpdateEvent::NewScreencapture(x));
+                    last.screencapture = notification.screencapture;
+                }
+                Err(err) => warn!(
+                    "Update Watcher: Failed to get screencapture meta. Got the following error: {}",
+                    err
+                ),
+            }
+        }
+        if notification.achievements != last.achievements {
+            self.events.push_back(UpdateEvent::AchievementsChanged);
+            last.achievements = notification.achievements;
+        }
+        self.last = Some(last);
+    }
+
+    async fn update_notification(&mut self) -> GenericResult<http_schemas::UpdateNotification> {
+        if self.token.is_none() {
+            self.token = self.watcher.client.new_token().await?;
+        }
+        self.watcher
+            .client
+            .get_update_notification(self.token.as_deref())
+            .await
+    }
+
+    fn mark_unreachable(&mut self, err: crate::utils::GenericError) {
+        // the console may have rebooted, so authenticate again once it is back
+        self.token = None;
+        let backoff = match self.backoff {
+            Some(x) => std::cmp::min(x.saturating_mul(2), self.watcher.max_backoff),
+            None => {
+                warn!(
+                    "Update Watcher: Console is unreachable. Got the following error: {}",
+                    err
+                );
+                self.events.push_back(UpdateEvent::ConsoleUnreachable);
+                std::cmp::min(self.watcher.interval, self.watcher.max_backoff)
+            }
+        };
+        debug!("Update Watcher: Retrying in {:?}.", backoff);
+        self.backoff = Some(backoff);
+    }
+}
@@ -0,0 +1,279 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::aurora::ftp::sync::{SyncDirection, SyncOptions};
+use crate::aurora::ftp::{AsyncFtpClient, FTPPath, FtpClient};
+use crate::aurora::http::HttpClient;
+use crate::aurora::http_schemas;
+use crate::telnet::TelnetClient;
+use crate::utils::GenericResult;
+use log::{debug, error};
+
+pub const DEFAULT_HTTP_PORT: usize = 9999;
+pub const DEFAULT_FTP_PORT: usize = 21;
+pub const DEFAULT_XBDM_PORT: u16 = 730;
+pub const DEFAULT_GAME_DATA_DIR: &str = "/Hdd1/Aurora/Data/GameData";
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct Capabilities: u32 {
+        const ACHIEVEMENTS = 0x001;
+        const DEBUGGER = 0x002;
+        const GAMEPAD = 0x004;
+        const HTTPDAEMON = 0x008;
+        const MULTIDISC = 0x010;
+        const NETWORK = 0x020;
+        const SYSTEMLINK = 0x040;
+        const THREADS = 0x080;
+        const TRAINERS = 0x100;
+    }
+}
+
+impl From<&http_schemas::PluginFeatures> for Capabilities {
+    fn from(features: &http_schemas::PluginFeatures) -> Self {
+        let mut capabilities = Self::empty();
+        capabilities.set(Self::ACHIEVEMENTS, features.achievements != 0);
+        capabilities.set(Self::DEBUGGER, features.debugger != 0);
+        capabilities.set(Self::GAMEPAD, features.gamepad != 0);
+        capabilities.set(Self::HTTPDAEMON, features.httpdaemon != 0);
+        capabilities.set(Self::MULTIDISC, features.multidisc != 0);
+        capabilities.set(Self::NETWORK, features.network != 0);
+        capabilities.set(Self::SYSTEMLINK, features.systemlink != 0);
+        capabilities.set(Self::THREADS, features.threads != 0);
+        capabilities.set(Self::TRAINERS, features.trainers != 0);
+        capabilities
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct ConsoleConfig {
+    pub name: String,
+    pub ip: String,
+    pub http_port: usize,
+    pub ftp_port: usize,
+    pub xbdm_port: u16,
+    pub http_username: Option<String>,
+    pub http_password: Option<String>,
+    pub ftp_username: Option<String>,
+    pub ftp_password: Option<String>,
+}
+
+impl ConsoleConfig {
+    pub fn new(name: &str, ip: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ip: ip.to_string(),
+            http_port: DEFAULT_HTTP_PORT,
+            ftp_port: DEFAULT_FTP_PORT,
+            xbdm_port: DEFAULT_XBDM_PORT,
+            http_username: None,
+            http_password: None,
+            ftp_username: None,
+            ftp_password: None,
+        }
+    }
+
+    pub fn http_port(mut self, port: usize) -> Self {
+        self.http_port = port;
+        self
+    }
+
+    pub fn ftp_port(mut self, port: usize) -> Self {
+        self.ftp_port = port;
+        self
+    }
+
+    pub fn xbdm_port(mut self, port: u16) -> Self {
+        self.xbdm_port = port;
+        self
+    }
+
+    pub fn http_credentials(mut self, username: &str, password: &str) -> Self {
+        self.http_username = Some(username.to_string());
+        self.http_password = Some(password.to_string());
+        self
+    }
+
+    pub fn ftp_credentials(mut self, username: &str, password: &str) -> Self {
+        self.ftp_username = Some(username.to_string());
+        self.ftp_password = Some(password.to_string());
+        self
+    }
+
+    pub fn http_client(&self) -> HttpClient {
+        HttpClient::new(
+            self.ip.clone(),
+            self.http_port,
+            self.http_username.clone(),
+            self.http_password.clone(),
+        )
+    }
+
+    pub fn ftp_client(&self) -> FtpClient {
+        FtpClient::new(
+            self.ip.clone(),
+            self.ftp_port,
+            self.ftp_username.clone(),
+            self.ftp_password.clone(),
+        )
+    }
+
+    pub fn async_ftp_client(&self) -> AsyncFtpClient {
+        AsyncFtpClient::new(
+            self.ip.clone(),
+            self.ftp_port,
+            self.ftp_username.clone(),
+            self.ftp_password.clone(),
+        )
+    }
+
+    pub fn telnet_client(&self) -> GenericResult<TelnetClient> {
+        TelnetClient::new(&self.ip, self.xbdm_port)
+    }
+}
+
+// the protocol clients are created on first use
+pub struct Console {
+    config: ConsoleConfig,
+    game_data_dir: String,
+    http: OnceLock<HttpClient>,
+    ftp: OnceLock<FtpClient>,
+    telnet: OnceLock<TelnetClient>,
+}
+
+impl From<ConsoleConfig> for Console {
+    fn from(config: ConsoleConfig) -> Self {
+        Self::new(config)
+    }
+}
+
+impl Console {
+    pub fn new(config: ConsoleConfig) -> Self {
+        Self {
+            config,
+            game_data_dir: DEFAULT_GAME_DATA_DIR.to_string(),
+            http: OnceLock::new(),
+            ftp: OnceLock::new(),
+            telnet: OnceLock::new(),
+        }
+    }
+
+    // FTP path of the directory Aurora stores title assets in
+    pub fn game_data_dir(mut self, game_data_dir: &str) -> Self {
+        self.game_data_dir = game_data_dir.to_string();
+        self
+    }
+
+    pub fn config(&self) -> &ConsoleConfig {
+        &self.config
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn http(&self) -> &HttpClient {
+        self.http.get_or_init(|| self.config.http_client())
+    }
+
+    pub fn ftp(&self) -> &FtpClient {
+        self.ftp.get_or_init(|| self.config.ftp_client())
+    }
+
+    pub fn telnet(&self) -> GenericResult<&TelnetClient> {
+        if let Some(x) = self.telnet.get() {
+            return Ok(x);
+        }
+        let client = self.config.telnet_client()?;
+        Ok(self.telnet.get_or_init(|| client))
+    }
+
+    pub async fn token(&self) -> GenericResult<Option<String>> {
+        self.http().new_token().await
+    }
+
+    pub async fn capabilities(&self) -> GenericResult<Capabilities> {
+        let token = self.token().await?;
+        let plugin = self.http().get_plugin(token.as_deref()).await?;
+        Ok(Capabilities::from(&plugin.features))
+    }
+
+    pub async fn current_title(&self) -> GenericResult<http_schemas::Title> {
+        let token = self.token().await?;
+        self.http().get_title(token.as_deref()).await
+    }
+
+    // downloads every GameData directory of the running title into
+    // `local_dir` and returns the local directories that were written.
+    // Aurora prefixes GameData directory names with the title id. existing
+    // local copies are updated in place and never removed, so a failed
+    // download leaves them intact.
+    pub async fn download_current_title_game_data(
+        &self,
+        local_dir: &Path,
+    ) -> GenericResult<Vec<PathBuf>> {
+        let title = self.current_title().await?;
+        let title_id = http_schemas::parse_hex_u32(&title.titleid)?;
+        if title_id == 0 {
+            let msg = "No title is currently running.";
+            error!("{}", msg);
+            return Err(msg.into());
+        }
+        let prefix = format!("{:08X}", title_id);
+        let ftp = self.ftp().clone();
+        let game_data_dir = self.game_data_dir.clone();
+        let local_dir = local_dir.to_path_buf();
+        let result = tokio::task::spawn_blocking(move || {
+            let mut downloaded = Vec::new();
+            for entry in ftp.list_directory_contents(None, &game_data_dir)? {
+                if !entry.is_directory() || !entry.name().to_uppercase().starts_with(&prefix) {
+                    continue;
+                }
+                let mut remote_path = FTPPath::from_str(&game_data_dir);
+                remote_path.push(entry.name());
+                let local_path = local_dir.join(entry.name());
+                debug!(
+                    "Downloading GameData '{}' to '{}'",
+                    remote_path.to_string(),
+                    local_path.display()
+                );
+                ftp.sync(
+                    None,
+                    &local_path,
+                    &remote_path.to_string(),
+                    &SyncOptions::new(SyncDirection::Download),
+                )?;
+                downloaded.push(local_path);
+            }
+            Ok::<Vec<PathBuf>, crate::utils::GenericError>(downloaded)
+        })
+        .await?;
+        let downloaded = result?;
+        if downloaded.is_empty() {
+            let msg = format!("No GameData found for title '{:08X}'.", title_id);
+            error!("{}", msg);
+            return Err(msg.into());
+        }
+        Ok(downloaded)
+    }
+}
@@ -0,0 +1,473 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO document functions
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::aurora::http_schemas;
+use crate::console::{ConsoleConfig, DEFAULT_FTP_PORT, DEFAULT_HTTP_PORT, DEFAULT_XBDM_PORT};
+use crate::utils::GenericResult;
+use futures::stream::{self, Stream, StreamExt};
+use log::{debug, error};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+
+const FTP_GREETING: &str = "220";
+const XBDM_GREETING: &str = "201- connected";
+// addresses above this count are rejected to avoid scanning huge networks by accident
+const MAX_TARGETS: u64 = 65536;
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct DiscoveredServices: u8 {
+        const HTTP = 0x01;
+        const FTP = 0x02;
+        const XBDM = 0x04;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DiscoveredConsole {
+    pub ip: Ipv4Addr,
+    pub services: DiscoveredServices,
+    pub http_port: usize,
+    pub ftp_port: usize,
+    pub xbdm_port: u16,
+    pub plugin: Option<http_schemas::Plugin>,
+    pub system: Option<http_schemas::System>,
+}
+
+impl DiscoveredConsole {
+    pub fn console_config(&self, name: &str) -> ConsoleConfig {
+        ConsoleConfig::new(name, &self.ip.to_string())
+            .http_port(self.http_port)
+            .ftp_port(self.ftp_port)
+            .xbdm_port(self.xbdm_port)
+    }
+}
+
+// parses a single address (`192.168.1.20`), an inclusive range
+// (`192.168.1.10-192.168.1.50` or `192.168.1.10-50`) or a CIDR block
+// (`192.168.1.0/24`). the network and broadcast addresses of CIDR blocks
+// with a prefix shorter than 31 are skipped.
+pub fn parse_targets(targets: &str) -> GenericResult<Vec<Ipv4Addr>> {
+    let targets = targets.trim();
+    let (start, end) = if let Some((address, prefix)) = targets.split_once('/') {
+        let address = parse_ipv4(address)?;
+        let prefix = match prefix.trim().parse::<u32>() {
+            Ok(x) if x <= 32 => x,
+            _ => {
+                let msg = format!("Invalid CIDR prefix '{}'.", prefix);
+                error!("{}", msg);
+                return Err(msg.into());
+            }
+        };
+        let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+        let network = u32::from(address) & mask;
+        let broadcast = network | !mask;
+        if prefix < 31 {
+            (network + 1, broadcast - 1)
+        } else {
+            (network, broadcast)
+        }
+    } else if let Some((first, last)) = targets.split_once('-') {
+        let first = parse_ipv4(first)?;
+        let last = match last.trim().parse::<u8>() {
+            Ok(x) => {
+                let octets = first.octets();
+                Ipv4Addr::new(octets[0], octets[1], octets[2], x)
+            }
+            Err(_) => parse_ipv4(last)?,
+        };
+        (u32::from(first), u32::from(last))
+    } else {
+        let address = u32::from(parse_ipv4(targets)?);
+        (address, address)
+    };
+    if start > end {
+        let msg = format!("Invalid address range '{}'.", targets);
+        error!("{}", msg);
+        return Err(msg.into());
+    }
+    if u64::from(end - start) + 1 > MAX_TARGETS {
+        let msg = format!(
+            "Address range '{}' exceeds {} addresses.",
+            targets, MAX_TARGETS
+        );
+        error!("{}", msg);
+        return Err(msg.into());
+    }
+    Ok((start..=end).map(Ipv4Addr::from).collect())
+}
+
+fn parse_ipv4(address: &str) -> GenericResult<Ipv4Addr> {
+    match Ipv4Addr::from_str(address.trim()) {
+        Ok(x) => Ok(x),
+        Err(err) => {
+            let msg = format!("Invalid IPv4 address '{}'.", address);
+            error!("{} Got the following error: {}", msg, err);
+            Err(msg.into())
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Discovery {
+    targets: Vec<Ipv4Addr>,
+    http_port: usize,
+    ftp_port: usize,
+    xbdm_port: u16,
+    http_username: Option<String>,
+    http_password: Option<String>,
+    concurrency: usize,
+    timeout: Duration,
+}
+
+impl Discovery {
+    pub fn new(targets: Vec<Ipv4Addr>) -> Self {
+        Self {
+            targets,
+            http_port: DEFAULT_HTTP_PORT,
+            ftp_port: DEFAULT_FTP_PORT,
+            xbdm_port: DEFAULT_XBDM_PORT,
+            http_username: None,
+            http_password: None,
+            concurrency: 64,
+            timeout: Duration::from_millis(1500),
+        }
+    }
+
+    pub fn from_targets(targets: &str) -> GenericResult<Self> {
+        Ok(Self::new(parse_targets(targets)?))
+    }
+
+    pub fn http_port(mut self, port: usize) -> Self {
+        self.http_port = port;
+        self
+    }
+
+    pub fn ftp_port(mut self, port: usize) -> Self {
+        self.ftp_port = port;
+        self
+    }
+
+    pub fn xbdm_port(mut self, port: u16) -> Self {
+        self.xbdm_port = port;
+        self
+    }
+
+    // used to authenticate before requesting `/plugin` and `/system`
+    pub fn http_credentials(mut self, username: &str, password: &str) -> Self {
+        self.http_username = Some(username.to_string());
+        self.http_password = Some(password.to_string());
+        self
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    // applies to each connection attempt and each request made to a host
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // yields consoles in the order they respond. hosts without any
+    // recognized service are not yielded.
+    pub fn into_stream(self) -> impl Stream<Item = DiscoveredConsole> {
+        let targets = self.targets.clone();
+        let concurrency = self.concurrency;
+        stream::iter(targets)
+            .map(move |ip| {
+                let discovery = self.clone();
+                async move { discovery.probe(ip).await }
+            })
+            .buffer_unordered(concurrency)
+            .filter_map(|x| async move { x })
+    }
+
+    pub async fn discover(self) -> Vec<DiscoveredConsole> {
+        self.into_stream().collect().await
+    }
+
+    pub async fn probe(&self, ip: Ipv4Addr) -> Option<DiscoveredConsole> {
+        let (http, ftp, xbdm) = futures::join!(
+            self.probe_http(ip),
+            self.probe_greeting(ip, self.ftp_port, FTP_GREETING),
+            self.probe_greeting(ip, self.xbdm_port as usize, XBDM_GREETING),
+        );
+        let mut services = DiscoveredServices::empty();
+        services.set(DiscoveredServices::HTTP, http.is_some());
+        services.set(DiscoveredServices::FTP, ftp);
+        services.set(DiscoveredServices::XBDM, xbdm);
+        if services.is_empty() {
+            return None;
+        }
+        let (plugin, system) = http.unwrap_or((None, None));
+        debug!(
+            "Discovered console at '{}' with services {:?}",
+            ip, services
+        );
+        Some(DiscoveredConsole {
+            ip,
+            services,
+            http_port: self.http_port,
+            ftp_port: self.ftp_port,
+            xbdm_port: self.xbdm_port,
+            plugin,
+            system,
+        })
+    }
+
+    fn console_config(&self, ip: Ipv4Addr) -> ConsoleConfig {
+        let config = ConsoleConfig::new(&ip.to_string(), &ip.to_string()).http_port(self.http_port);
+        match (&self.http_username, &self.http_password) {
+            (Some(username), Some(password)) => config.http_credentials(username, password),
+            _ => config,
+        }
+    }
+
+    // returns `None` when no Aurora HTTP server answered. a server that
+    // rejects the request as unauthorized is still reported, without identity.
+    async fn probe_http(
+        &self,
+        ip: Ipv4Addr,
+    ) -> Option<(Option<http_schemas::Plugin>, Option<http_schemas::System>)> {
+        let client = self.console_config(ip).http_client();
+        let token = match tokio::time::timeout(self.timeout, client.new_token()).await {
+            Ok(Ok(x)) => x,
+            Ok(Err(err)) => {
+                debug!(
+                    "Failed to authenticate with '{}'. Got the following error: {}",
+                    ip, err
+                );
+                None
+            }
+            Err(_) => None,
+        };
+        let token = token.as_deref();
+        let plugin = match tokio::time::timeout(self.timeout, client.get_plugin(token)).await {
+            Ok(Ok(x)) => x,
+            _ => {
+                return match self.http_unauthorized(ip).await {
+                    true => Some((None, None)),
+                    false => None,
+                };
+            }
+        };
+        let system = match tokio::time::timeout(self.timeout, client.get_system(token)).await {
+            Ok(Ok(x)) => Some(x),
+            _ => None,
+        };
+        Some((Some(plugin), system))
+    }
+
+    async fn http_unauthorized(&self, ip: Ipv4Addr) -> bool {
+        let url = format!("http://{}:{}/plugin", ip, self.http_port);
+        match tokio::time::timeout(self.timeout, reqwest::get(url)).await {
+            Ok(Ok(x)) => x.status() == reqwest::StatusCode::UNAUTHORIZED,
+            _ => false,
+        }
+    }
+
+    async fn probe_greeting(&self, ip: Ipv4Addr, port: usize, greeting: &str) -> bool {
+        let port = match u16::try_from(port) {
+            Ok(x) => x,
+            Err(_) => return false,
+        };
+        let address = SocketAddr::new(IpAddr::V4(ip), port);
+        let read_greeting = async {
+            let mut stream = TcpStream::connect(address).await?;
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 256];
+            while buffer.len() < greeting.len() {
+                let n = stream.read(&mut chunk).await?;
+                if n == 0 {
+                    break;
+                }
+                buffer.extend_from_slice(&chunk[..n]);
+            }
+            Ok::<Vec<u8>, std::io::Error>(buffer)
+        };
+        match tokio::time::timeout(self.timeout, read_greeting).await {
+            Ok(Ok(x)) => String::from_utf8_lossy(&x).starts_with(greeting),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{spawn_greeting_stub, spawn_http_stub, StubRoute};
+
+    const PLUGIN: &str = r#"{"features":{"achievements":1,"debugger":1,"gamepad":1,"httpdaemon":1,"multidisc":1,"network":1,"systemlink":1,"threads":1,"trainers":1},"path":{"launcher":"Hdd1:\\Aurora\\Aurora.xex","root":"Hdd1:\\Aurora","user":"Hdd1:\\Aurora\\User","web":"Hdd1:\\Aurora\\Plugins\\Web"},"version":{"api":2,"number":{"build":1,"major":0,"minor":7,"type":0}}}"#;
+    const SYSTEM: &str = r#"{"console":{"motherboard":"Jasper","type":"Retail"},"consoleid":"1","cpukey":"2","dvdkey":"3","serial":"4","version":{"build":17559,"major":2,"minor":0,"qfe":0}}"#;
+
+    fn http_routes() -> Vec<StubRoute> {
+        vec![
+            StubRoute::new("POST", "/authenticate", 200, r#"{"token":"t"}"#),
+            StubRoute::new("GET", "/plugin", 200, PLUGIN),
+            StubRoute::new("GET", "/system", 200, SYSTEM),
+        ]
+    }
+
+    // a port that nothing listens on
+    async fn closed_port() -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    fn discovery(http_port: u16, ftp_port: u16, xbdm_port: u16) -> Discovery {
+        Discovery::new(vec![Ipv4Addr::LOCALHOST])
+            .http_port(http_port as usize)
+            .ftp_port(ftp_port as usize)
+            .xbdm_port(xbdm_port)
+            .timeout(Duration::from_secs(5))
+    }
+
+    #[test]
+    fn parse_single_address() {
+        assert_eq!(
+            parse_targets(" 192.168.1.20 ").unwrap(),
+            vec![Ipv4Addr::new(192, 168, 1, 20)]
+        );
+    }
+
+    #[test]
+    fn parse_ranges() {
+        let full = parse_targets("192.168.1.10-192.168.1.12").unwrap();
+        let short = parse_targets("192.168.1.10-12").unwrap();
+        assert_eq!(
+            full,
+            vec![
+                Ipv4Addr::new(192, 168, 1, 10),
+                Ipv4Addr::new(192, 168, 1, 11),
+                Ipv4Addr::new(192, 168, 1, 12),
+            ]
+        );
+        assert_eq!(full, short);
+        assert_eq!(
+            parse_targets("10.0.0.255-10.0.1.0").unwrap(),
+            vec![Ipv4Addr::new(10, 0, 0, 255), Ipv4Addr::new(10, 0, 1, 0)]
+        );
+    }
+
+    #[test]
+    fn parse_cidr_blocks() {
+        let block = parse_targets("192.168.1.77/24").unwrap();
+        assert_eq!(block.len(), 254);
+        assert_eq!(block[0], Ipv4Addr::new(192, 168, 1, 1));
+        assert_eq!(block[253], Ipv4Addr::new(192, 168, 1, 254));
+        assert_eq!(
+            parse_targets("10.0.0.4/30").unwrap(),
+            vec![Ipv4Addr::new(10, 0, 0, 5), Ipv4Addr::new(10, 0, 0, 6)]
+        );
+        assert_eq!(
+            parse_targets("10.0.0.4/31").unwrap(),
+            vec![Ipv4Addr::new(10, 0, 0, 4), Ipv4Addr::new(10, 0, 0, 5)]
+        );
+        assert_eq!(
+            parse_targets("10.0.0.4/32").unwrap(),
+            vec![Ipv4Addr::new(10, 0, 0, 4)]
+        );
+        assert_eq!(parse_targets("10.0.0.0/16").unwrap().len(), 65534);
+    }
+
+    #[test]
+    fn parse_bad_input() {
+        for targets in [
+            "",
+            "console",
+            "192.168.1",
+            "192.168.1.256",
+            "192.168.1.0/33",
+            "192.168.1.0/x",
+            "192.168.1.20-192.168.1.10",
+            "192.168.1.20-10",
+            "192.168.1.10-300",
+            "10.0.0.0/8",
+            "::1",
+        ] {
+            assert!(parse_targets(targets).is_err(), "accepted '{}'", targets);
+        }
+    }
+
+    #[tokio::test]
+    async fn probe_all_services() {
+        let http = spawn_http_stub(http_routes(), Some("t")).await;
+        let ftp = spawn_greeting_stub("220 Aurora FTP ready\r\n").await;
+        let xbdm = spawn_greeting_stub("201- connected\r\n").await;
+        let console = discovery(http.port(), ftp.port(), xbdm.port())
+            .http_credentials("user", "pass")
+            .probe(Ipv4Addr::LOCALHOST)
+            .await
+            .unwrap();
+        assert_eq!(console.services, DiscoveredServices::all());
+        assert_eq!(console.plugin.as_ref().unwrap().version.api, 2);
+        assert_eq!(
+            console.system.as_ref().unwrap().console.motherboard,
+            "Jasper"
+        );
+        let config = console.console_config("found");
+        assert_eq!(config.http_port, http.port() as usize);
+        assert_eq!(config.xbdm_port, xbdm.port());
+    }
+
+    #[tokio::test]
+    async fn probe_protected_http_without_credentials() {
+        let http = spawn_http_stub(http_routes(), Some("t")).await;
+        let console = discovery(http.port(), closed_port().await, closed_port().await)
+            .probe(Ipv4Addr::LOCALHOST)
+            .await
+            .unwrap();
+        assert_eq!(console.services, DiscoveredServices::HTTP);
+        assert!(console.plugin.is_none());
+        assert!(console.system.is_none());
+    }
+
+    #[tokio::test]
+    async fn probe_rejects_other_services() {
+        let http = spawn_http_stub(Vec::new(), None).await;
+        let ftp = spawn_greeting_stub("SSH-2.0-OpenSSH\r\n").await;
+        let xbdm = spawn_greeting_stub("220 not xbdm\r\n").await;
+        let probe = discovery(http.port(), ftp.port(), xbdm.port())
+            .probe(Ipv4Addr::LOCALHOST)
+            .await;
+        assert!(probe.is_none());
+    }
+
+    #[tokio::test]
+    async fn discover_streams_found_hosts() {
+        let ftp = spawn_greeting_stub("220 ready\r\n").await;
+        let found = Discovery::new(vec![Ipv4Addr::LOCALHOST, Ipv4Addr::new(127, 0, 0, 2)])
+            .http_port(closed_port().await as usize)
+            .ftp_port(ftp.port() as usize)
+            .xbdm_port(closed_port().await)
+            .timeout(Duration::from_secs(5))
+            .discover()
+            .await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].ip, Ipv4Addr::LOCALHOST);
+        assert_eq!(found[0].services, DiscoveredServices::FTP);
+    }
+}
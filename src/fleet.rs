@@ -0,0 +1,229 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::aurora::http_schemas;
+use crate::console::ConsoleConfig;
+use crate::utils::{GenericError, GenericResult};
+use futures::stream::{self, StreamExt};
+use log::{debug, error};
+
+#[derive(Debug)]
+pub struct FleetResult<T> {
+    pub successes: BTreeMap<String, T>,
+    pub failures: BTreeMap<String, GenericError>,
+}
+
+impl<T> FleetResult<T> {
+    fn new() -> Self {
+        Self {
+            successes: BTreeMap::new(),
+            failures: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.successes.len() + self.failures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fleet {
+    consoles: BTreeMap<String, ConsoleConfig>,
+    concurrency: usize,
+    timeout: Duration,
+}
+
+impl Default for Fleet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fleet {
+    pub fn new() -> Self {
+        Self {
+            consoles: BTreeMap::new(),
+            concurrency: 4,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // replaces any console that was registered with the same name
+    pub fn add_console(&mut self, config: ConsoleConfig) -> Option<ConsoleConfig> {
+        self.consoles.insert(config.name.clone(), config)
+    }
+
+    pub fn remove_console(&mut self, name: &str) -> Option<ConsoleConfig> {
+        self.consoles.remove(name)
+    }
+
+    pub fn console(&self, name: &str) -> Option<&ConsoleConfig> {
+        self.consoles.get(name)
+    }
+
+    pub fn consoles(&self) -> impl Iterator<Item = &ConsoleConfig> {
+        self.consoles.values()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.consoles.keys().cloned().collect()
+    }
+
+    // `names` selects a subset of the fleet, `None` selects every console.
+    // names that are not part of the fleet are reported as failures.
+    fn select(&self, names: Option<&[&str]>) -> Vec<(String, Option<ConsoleConfig>)> {
+        match names {
+            Some(names) => names
+                .iter()
+                .map(|x| (x.to_string(), self.consoles.get(*x).cloned()))
+                .collect(),
+            None => self
+                .consoles
+                .iter()
+                .map(|(name, config)| (name.clone(), Some(config.clone())))
+                .collect(),
+        }
+    }
+
+    pub async fn run<F, Fut, T>(&self, names: Option<&[&str]>, f: F) -> FleetResult<T>
+    where
+        F: Fn(ConsoleConfig) -> Fut,
+        Fut: Future<Output = GenericResult<T>>,
+    {
+        let timeout = self.timeout;
+        let f = &f;
+        let results = stream::iter(self.select(names))
+            .map(|(name, config)| async move {
+                let result = match config {
+                    Some(config) => match tokio::time::timeout(timeout, f(config)).await {
+                        Ok(x) => x,
+                        Err(_) => Err(format!("Timed out after {:?}.", timeout).into()),
+                    },
+                    None => Err(format!("Unknown console '{}'.", name).into()),
+                };
+                (name, result)
+            })
+            .buffer_unordered(self.concurrency)
+            .collect::<Vec<_>>()
+            .await;
+        Self::aggregate(results)
+    }
+
+    // runs `f` on the blocking thread pool. a call that exceeds the timeout is
+    // reported as a failure, but the thread keeps running until `f` returns.
+    pub async fn run_blocking<F, T>(&self, names: Option<&[&str]>, f: F) -> FleetResult<T>
+    where
+        F: Fn(ConsoleConfig) -> GenericResult<T> + Clone + Send + 'static,
+        T: Send + 'static,
+    {
+        self.run(names, |config| {
+            let f = f.clone();
+            async move {
+                match tokio::task::spawn_blocking(move || f(config)).await {
+                    Ok(x) => x,
+                    Err(err) => Err(err.into()),
+                }
+            }
+        })
+        .await
+    }
+
+    fn aggregate<T>(results: Vec<(String, GenericResult<T>)>) -> FleetResult<T> {
+        let mut fleet_result = FleetResult::new();
+        for (name, result) in results {
+            match result {
+                Ok(x) => {
+                    debug!("Fleet operation succeeded on console '{}'", name);
+                    fleet_result.successes.insert(name, x);
+                }
+                Err(err) => {
+                    error!(
+                        "Fleet operation failed on console '{}'. Got the following error: {}",
+                        name, err
+                    );
+                    fleet_result.failures.insert(name, err);
+                }
+            }
+        }
+        fleet_result
+    }
+
+    pub async fn get_system(&self, names: Option<&[&str]>) -> FleetResult<http_schemas::System> {
+        self.run(names, |config| async move {
+            let client = config.http_client();
+            let token = client.new_token().await?;
+            client.get_system(token.as_deref()).await
+        })
+        .await
+    }
+
+    pub async fn get_title(&self, names: Option<&[&str]>) -> FleetResult<http_schemas::Title> {
+        self.run(names, |config| async move {
+            let client = config.http_client();
+            let token = client.new_token().await?;
+            client.get_title(token.as_deref()).await
+        })
+        .await
+    }
+
+    pub async fn upload_file(
+        &self,
+        names: Option<&[&str]>,
+        local_file: &Path,
+        remote_file: &str,
+    ) -> FleetResult<()> {
+        let local_file = PathBuf::from(local_file);
+        let remote_file = remote_file.to_string();
+        self.run_blocking(names, move |config| {
+            config
+                .ftp_client()
+                .upload_file(None, &local_file, &remote_file)
+        })
+        .await
+    }
+
+    pub async fn dvdeject(&self, names: Option<&[&str]>) -> FleetResult<()> {
+        self.run_blocking(names, |config| config.telnet_client()?.dvdeject())
+            .await
+    }
+}
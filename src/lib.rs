@@ -17,7 +17,13 @@
  * libaustralis. If not, see <https://www.gnu.org/licenses/>.
  */
 pub mod aurora;
+pub mod console;
+pub mod discovery;
+pub mod fleet;
+pub mod profiles;
 pub mod telnet;
+#[cfg(test)]
+mod testing;
 pub mod utils;
 pub mod xboxcatalog;
 pub mod xboxunity;
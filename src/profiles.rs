@@ -0,0 +1,289 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::aurora::ftp::FtpClient;
+use crate::aurora::http::HttpClient;
+use crate::console::{ConsoleConfig, DEFAULT_FTP_PORT, DEFAULT_HTTP_PORT, DEFAULT_XBDM_PORT};
+use crate::telnet::TelnetClient;
+use crate::utils::GenericResult;
+use log::{debug, error, warn};
+
+pub const PROFILES_DIR_NAME: &str = "libaustralis";
+pub const PROFILES_FILE_NAME: &str = "profiles.toml";
+pub const SECRETS_FILE_NAME: &str = "secrets.toml";
+
+fn default_http_port() -> usize {
+    DEFAULT_HTTP_PORT
+}
+
+fn default_ftp_port() -> usize {
+    DEFAULT_FTP_PORT
+}
+
+fn default_xbdm_port() -> u16 {
+    DEFAULT_XBDM_PORT
+}
+
+// everything about a console except its passwords
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Profile {
+    pub host: String,
+    #[serde(default = "default_http_port")]
+    pub http_port: usize,
+    #[serde(default = "default_ftp_port")]
+    pub ftp_port: usize,
+    #[serde(default = "default_xbdm_port")]
+    pub xbdm_port: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ftp_username: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ProfileSecrets {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ftp_password: Option<String>,
+}
+
+impl ProfileSecrets {
+    fn is_empty(&self) -> bool {
+        self.http_password.is_none() && self.ftp_password.is_none()
+    }
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    consoles: BTreeMap<String, Profile>,
+}
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+struct SecretsFile {
+    #[serde(default)]
+    consoles: BTreeMap<String, ProfileSecrets>,
+}
+
+// profiles are stored in `profiles.toml` and their passwords in
+// `secrets.toml`, which is only readable by the owner on unix systems
+#[derive(Clone, Debug, Default)]
+pub struct Profiles {
+    dir: PathBuf,
+    profiles: BTreeMap<String, Profile>,
+    secrets: BTreeMap<String, ProfileSecrets>,
+}
+
+impl Profiles {
+    pub fn default_dir() -> GenericResult<PathBuf> {
+        match dirs::config_dir() {
+            Some(x) => Ok(x.join(PROFILES_DIR_NAME)),
+            None => {
+                let msg = "Failed to determine the user's config directory.";
+                error!("{}", msg);
+                Err(msg.into())
+            }
+        }
+    }
+
+    pub fn load() -> GenericResult<Self> {
+        Self::load_from(&Self::default_dir()?)
+    }
+
+    // missing files are treated as empty
+    pub fn load_from(dir: &Path) -> GenericResult<Self> {
+        let profiles_file = dir.join(PROFILES_FILE_NAME);
+        let secrets_file = dir.join(SECRETS_FILE_NAME);
+        let profiles = match profiles_file.is_file() {
+            true => toml::from_str::<ProfilesFile>(&std::fs::read_to_string(&profiles_file)?)?,
+            false => ProfilesFile::default(),
+        };
+        let secrets = match secrets_file.is_file() {
+            true => toml::from_str::<SecretsFile>(&std::fs::read_to_string(&secrets_file)?)?,
+            false => SecretsFile::default(),
+        };
+        for name in secrets.consoles.keys() {
+            if !profiles.consoles.contains_key(name) {
+                warn!("Secrets are defined for unknown profile '{}'", name);
+            }
+        }
+        debug!(
+            "Loaded {} profile(s) from '{}'",
+            profiles.consoles.len(),
+            dir.display()
+        );
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            profiles: profiles.consoles,
+            secrets: secrets.consoles,
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn save(&self) -> GenericResult<()> {
+        self.save_to(&self.dir)
+    }
+
+    pub fn save_to(&self, dir: &Path) -> GenericResult<()> {
+        std::fs::create_dir_all(dir)?;
+        let profiles = ProfilesFile {
+            consoles: self.profiles.clone(),
+        };
+        let secrets = SecretsFile {
+            consoles: self
+                .secrets
+                .iter()
+                .filter(|(name, secrets)| self.profiles.contains_key(*name) && !secrets.is_empty())
+                .map(|(name, secrets)| (name.clone(), secrets.clone()))
+                .collect(),
+        };
+        write_file(
+            &dir.join(PROFILES_FILE_NAME),
+            &toml::to_string_pretty(&profiles)?,
+            false,
+        )?;
+        write_file(
+            &dir.join(SECRETS_FILE_NAME),
+            &toml::to_string_pretty(&secrets)?,
+            true,
+        )
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+
+    pub fn secrets(&self, name: &str) -> Option<&ProfileSecrets> {
+        self.secrets.get(name)
+    }
+
+    // replaces the profile and secrets that were stored with the same name
+    pub fn insert(&mut self, config: &ConsoleConfig) {
+        self.profiles.insert(
+            config.name.clone(),
+            Profile {
+                host: config.ip.clone(),
+                http_port: config.http_port,
+                ftp_port: config.ftp_port,
+                xbdm_port: config.xbdm_port,
+                http_username: config.http_username.clone(),
+                ftp_username: config.ftp_username.clone(),
+            },
+        );
+        self.secrets.insert(
+            config.name.clone(),
+            ProfileSecrets {
+                http_password: config.http_password.clone(),
+                ftp_password: config.ftp_password.clone(),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<ConsoleConfig> {
+        let config = self.console_config(name);
+        self.profiles.remove(name);
+        self.secrets.remove(name);
+        config
+    }
+
+    pub fn console_config(&self, name: &str) -> Option<ConsoleConfig> {
+        let profile = self.profiles.get(name)?;
+        let secrets = self.secrets.get(name).cloned().unwrap_or_default();
+        Some(ConsoleConfig {
+            name: name.to_string(),
+            ip: profile.host.clone(),
+            http_port: profile.http_port,
+            ftp_port: profile.ftp_port,
+            xbdm_port: profile.xbdm_port,
+            http_username: profile.http_username.clone(),
+            http_password: secrets.http_password,
+            ftp_username: profile.ftp_username.clone(),
+            ftp_password: secrets.ftp_password,
+        })
+    }
+
+    pub fn console_configs(&self) -> Vec<ConsoleConfig> {
+        self.profiles
+            .keys()
+            .filter_map(|x| self.console_config(x))
+            .collect()
+    }
+
+    pub fn http_client(&self, name: &str) -> GenericResult<HttpClient> {
+        Ok(self.require(name)?.http_client())
+    }
+
+    pub fn ftp_client(&self, name: &str) -> GenericResult<FtpClient> {
+        Ok(self.require(name)?.ftp_client())
+    }
+
+    pub fn telnet_client(&self, name: &str) -> GenericResult<TelnetClient> {
+        self.require(name)?.telnet_client()
+    }
+
+    fn require(&self, name: &str) -> GenericResult<ConsoleConfig> {
+        match self.console_config(name) {
+            Some(x) => Ok(x),
+            None => {
+                let msg = format!("Unknown profile '{}'.", name);
+                error!("{}", msg);
+                Err(msg.into())
+            }
+        }
+    }
+}
+
+// writes to a temporary file that is renamed over `file_path`, so readers
+// never see a partially written file
+fn write_file(file_path: &Path, contents: &str, private: bool) -> GenericResult<()> {
+    let temp_file = file_path.with_extension("toml.part");
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let mut file = options.open(&temp_file)?;
+    // the mode only applies when the file is created
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&temp_file, file_path)?;
+    Ok(())
+}
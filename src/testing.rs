@@ -0,0 +1,165 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// helpers for tests that need a console listening on loopback
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Clone, Debug)]
+pub(crate) struct StubRoute {
+    method: &'static str,
+    path: &'static str,
+    status: u16,
+    body: String,
+}
+
+impl StubRoute {
+    pub(crate) fn new(method: &'static str, path: &'static str, status: u16, body: &str) -> Self {
+        Self {
+            method,
+            path,
+            status,
+            body: String::from(body),
+        }
+    }
+}
+
+// serves `routes` over HTTP on an ephemeral loopback port. when `token` is set,
+// every route except `POST /authenticate` requires it as a bearer token.
+pub(crate) async fn spawn_http_stub(
+    routes: Vec<StubRoute>,
+    token: Option<&'static str>,
+) -> SocketAddr {
+    spawn_recording_http_stub(routes, token).await.0
+}
+
+// like `spawn_http_stub`, also returning every request received as
+// "METHOD path body"
+pub(crate) async fn spawn_recording_http_stub(
+    routes: Vec<StubRoute>,
+    token: Option<&'static str>,
+) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let routes = Arc::new(routes);
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = requests.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let routes = routes.clone();
+            let log = log.clone();
+            tokio::spawn(async move {
+                let _ = handle_http(stream, &routes, token, &log).await;
+            });
+        }
+    });
+    (address, requests)
+}
+
+async fn handle_http(
+    mut stream: TcpStream,
+    routes: &[StubRoute],
+    token: Option<&str>,
+    log: &Mutex<Vec<String>>,
+) -> std::io::Result<()> {
+    let mut request: Vec<u8> = Vec::new();
+    let mut buf = [0u8; 1024];
+    let head_length = loop {
+        if let Some(x) = request.windows(4).position(|x| x == b"\r\n\r\n") {
+            break x + 4;
+        }
+        let bytes_read = stream.read(&mut buf).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+        request.extend_from_slice(&buf[..bytes_read]);
+    };
+    let head = String::from_utf8_lossy(&request[..head_length]).to_string();
+    let content_length = header(&head, "content-length")
+        .and_then(|x| x.parse::<usize>().ok())
+        .unwrap_or(0);
+    while request.len() < head_length + content_length {
+        let bytes_read = stream.read(&mut buf).await?;
+        if bytes_read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..bytes_read]);
+    }
+    let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line
+        .next()
+        .unwrap_or("")
+        .split('?')
+        .next()
+        .unwrap_or("");
+    let body = String::from_utf8_lossy(&request[head_length..]);
+    log.lock()
+        .unwrap()
+        .push(format!("{} {} {}", method, path, body));
+    let authorized = match token {
+        Some(x) => {
+            (method, path) == ("POST", "/authenticate")
+                || header(&head, "authorization") == Some(format!("Bearer {}", x))
+        }
+        None => true,
+    };
+    let (status, body) = match routes.iter().find(|x| x.method == method && x.path == path) {
+        Some(_) if !authorized => (401, String::new()),
+        Some(x) => (x.status, x.body.clone()),
+        None => (404, String::new()),
+    };
+    let response = format!(
+        "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn header(head: &str, name: &str) -> Option<String> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        match key.trim().eq_ignore_ascii_case(name) {
+            true => Some(value.trim().to_string()),
+            false => None,
+        }
+    })
+}
+
+// accepts connections on an ephemeral loopback port and sends `greeting` to
+// each of them
+pub(crate) async fn spawn_greeting_stub(greeting: &'static str) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let _ = stream.write_all(greeting.as_bytes()).await;
+                let mut buf = [0u8; 256];
+                let _ = stream.read(&mut buf).await;
+            });
+        }
+    });
+    address
+}
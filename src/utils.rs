@@ -24,6 +24,17 @@ use std::path::Path;
 pub type GenericError = Box<dyn std::error::Error + Send + Sync + 'static>;
 pub type GenericResult<T> = Result<T, GenericError>;
 
+// runs `future` to completion on a new current-thread runtime
+pub(crate) fn block_on<F, T>(future: F) -> GenericResult<T>
+where
+    F: std::future::Future<Output = GenericResult<T>>,
+{
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(future)
+}
+
 pub fn create_parent_directories(file_path: &Path) -> GenericResult<()> {
     match Path::new(file_path).parent() {
         Some(parent_path) => match std::fs::create_dir_all(parent_path) {
@@ -25,6 +25,9 @@ use crate::utils::GenericResult;
 use log::{error, warn};
 use serde;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 pub const API_ROOT: &str = "https://catalog.xboxlive.com";
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
@@ -61,6 +64,19 @@ impl LiveImage {
     }
 }
 
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDetails {
+    pub full_title: Option<String>,
+    pub reduced_title: Option<String>,
+    pub full_description: Option<String>,
+    pub reduced_description: Option<String>,
+    pub developer_name: Option<String>,
+    pub publisher_name: Option<String>,
+    pub release_date: Option<String>,
+    pub genres: Vec<String>,
+}
+
 pub async fn find_games_xml(media_id: &str, locale: Locale) -> GenericResult<String> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(60))
@@ -191,6 +207,13 @@ pub async fn live_images_for_title_id(
     live_images_from_xml(&find_games_xml(&media_id_for_title_id(title_id), locale).await?)
 }
 
+pub async fn game_details_for_title_id(
+    title_id: usize,
+    locale: Locale,
+) -> GenericResult<GameDetails> {
+    game_details_from_xml(&find_games_xml(&media_id_for_title_id(title_id), locale).await?)
+}
+
 pub fn media_id_for_title_id(title_id: usize) -> String {
     format!("66acd000-77fe-1000-9115-d802{:08x}", title_id)
 }
@@ -300,6 +323,60 @@ pub fn live_images_from_xml(xml: &str) -> GenericResult<Vec<LiveImage>> {
     Ok(live_images)
 }
 
+pub fn game_details_from_xml(xml: &str) -> GenericResult<GameDetails> {
+    let mut details = GameDetails::default();
+    let mut element_name: String = String::from("");
+    let mut category_depth: usize = 0;
+    for reader_element in xml::reader::EventReader::from_str(xml) {
+        match reader_element {
+            Ok(xml::reader::XmlEvent::StartElement { name, .. }) => {
+                element_name = name.local_name;
+                if element_name == "category" {
+                    category_depth += 1;
+                }
+            }
+            Ok(xml::reader::XmlEvent::EndElement { name }) => {
+                element_name = String::from("");
+                if name.local_name == "category" {
+                    category_depth = category_depth.saturating_sub(1);
+                } else if name.local_name == "entry" {
+                    break;
+                }
+            }
+            Ok(xml::reader::XmlEvent::Characters(s)) => {
+                // only the first occurrence of each value is kept since the
+                // entry also contains nested elements with the same names
+                let value = match element_name.as_str() {
+                    "fullTitle" => &mut details.full_title,
+                    "reducedTitle" => &mut details.reduced_title,
+                    "fullDescription" => &mut details.full_description,
+                    "reducedDescription" => &mut details.reduced_description,
+                    "developerName" => &mut details.developer_name,
+                    "publisherName" => &mut details.publisher_name,
+                    "releaseDate" => &mut details.release_date,
+                    "name" if category_depth > 0 => {
+                        if !details.genres.contains(&s) {
+                            details.genres.push(s);
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+                if value.is_none() {
+                    *value = Some(s);
+                }
+            }
+            Err(err) => {
+                let msg = "Failed to parse xml.";
+                error!("{} Got the following error: {}", msg, err);
+                return Err(msg.into());
+            }
+            _ => {}
+        }
+    }
+    Ok(details)
+}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(usize)]
 pub enum Locale {
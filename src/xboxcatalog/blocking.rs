@@ -0,0 +1,40 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+// synchronous mirrors of the `xboxcatalog` functions. they must not be called
+// from within an async context.
+use super::{GameDetails, LiveImage, Locale};
+use crate::utils::{block_on, GenericResult};
+
+pub fn find_games_xml(media_id: &str, locale: Locale) -> GenericResult<String> {
+    block_on(super::find_games_xml(media_id, locale))
+}
+
+pub fn live_image_file_bytes(live_image: LiveImage) -> GenericResult<Vec<u8>> {
+    block_on(super::live_image_file_bytes(live_image))
+}
+
+pub fn live_images_for_title_id(title_id: usize, locale: Locale) -> GenericResult<Vec<LiveImage>> {
+    block_on(super::live_images_for_title_id(title_id, locale))
+}
+
+pub fn game_details_for_title_id(title_id: usize, locale: Locale) -> GenericResult<GameDetails> {
+    block_on(super::game_details_for_title_id(title_id, locale))
+}
@@ -26,6 +26,9 @@ use crate::utils::GenericResult;
 use log::error;
 use serde;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
 pub const API_ROOT: &str = "https://xboxunity.net";
 
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
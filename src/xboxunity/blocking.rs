@@ -0,0 +1,53 @@
+/**
+ * Copyright 2025-2026 jrobiche
+ *
+ * This file is part of libaustralis.
+ *
+ * libaustrais is free software: you can redistribute it and/or modify it under
+ * the terms of the GNU General Public License as published by the Free
+ * Software Foundation, either version 3 of the License, or (at your option)
+ * any later version.
+ *
+ * libaustralis is distributed in the hope that it will be useful, but WITHOUT
+ * ANY WARRANTY; without even the implied warranty of MERCHANTABILITY or
+ * FITNESS FOR A PARTICULAR PURPOSE. See the GNU General Public License for
+ * more details.
+ *
+ * You should have received a copy of the GNU General Public License along with
+ * libaustralis. If not, see <https://www.gnu.org/licenses/>.
+ */
+// TODO define tests
+// TODO document functions
+// synchronous mirrors of the `xboxunity` functions. they must not be called
+// from within an async context.
+use super::{
+    CoverInfoResult, CoverSize, SearchCategory, SearchDirection, SearchFilter, SearchSort,
+    TitleListResult,
+};
+use crate::utils::{block_on, GenericResult};
+
+pub fn cover_info(title_id: usize) -> GenericResult<CoverInfoResult> {
+    block_on(super::cover_info(title_id))
+}
+
+pub fn title_list(
+    search: &str,
+    page: usize,
+    count: usize,
+    sort: SearchSort,
+    direction: SearchDirection,
+    category: SearchCategory,
+    filter: SearchFilter,
+) -> GenericResult<TitleListResult> {
+    block_on(super::title_list(
+        search, page, count, sort, direction, category, filter,
+    ))
+}
+
+pub fn cover_image_bytes(cover_id: usize, cover_size: CoverSize) -> GenericResult<Vec<u8>> {
+    block_on(super::cover_image_bytes(cover_id, cover_size))
+}
+
+pub fn icon_image_bytes(title_id: usize) -> GenericResult<Vec<u8>> {
+    block_on(super::icon_image_bytes(title_id))
+}